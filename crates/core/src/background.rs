@@ -0,0 +1,152 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{color::Color, hash_floats};
+
+/// Color at a point of a gradient, `offset` goes from 0.0 (start) to 1.0 (end)
+#[derive(Debug, Clone, Copy)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+impl Hash for GradientStop {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_floats(&[self.offset], state);
+        self.color.hash(state);
+    }
+}
+
+impl GradientStop {
+    pub fn new(offset: f32, color: Color) -> Self {
+        Self {
+            offset: offset.clamp(0.0, 1.0),
+            color,
+        }
+    }
+}
+
+/// Fill of a container, a button or the window
+#[derive(Debug, Clone)]
+pub enum Background {
+    Color(Color),
+
+    /// Colors blend along a line through the center of the widget
+    LinearGradient {
+        /// Direction in degrees, 0 goes from left to right and 90 from top to bottom
+        angle: f32,
+        stops: Vec<GradientStop>,
+    },
+
+    /// Colors blend in circles around the center
+    RadialGradient {
+        /// Position relative to the widget, (0.5, 0.5) is the middle
+        center: (f32, f32),
+        /// 1.0 reaches the corner farthest from the center
+        radius: f32,
+        stops: Vec<GradientStop>,
+    },
+
+    /// Colors blend clockwise around the center
+    SweepGradient {
+        /// Position relative to the widget, (0.5, 0.5) is the middle
+        center: (f32, f32),
+        /// Where the sweep starts in degrees, 0 points to the right
+        angle: f32,
+        stops: Vec<GradientStop>,
+    },
+}
+
+impl Hash for Background {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Background::Color(color) => color.hash(state),
+            Background::LinearGradient { angle, stops } => {
+                hash_floats(&[*angle], state);
+                stops.hash(state);
+            }
+            Background::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => {
+                hash_floats(&[center.0, center.1, *radius], state);
+                stops.hash(state);
+            }
+            Background::SweepGradient {
+                center,
+                angle,
+                stops,
+            } => {
+                hash_floats(&[center.0, center.1, *angle], state);
+                stops.hash(state);
+            }
+        }
+    }
+}
+
+impl Background {
+    /// Linear gradient, `stops` are (offset, color) pairs
+    pub fn linear(angle: f32, stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        Self::LinearGradient {
+            angle,
+            stops: gradient_stops(stops),
+        }
+    }
+
+    /// Radial gradient from the middle of the widget, `stops` are (offset, color) pairs
+    pub fn radial(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        Self::RadialGradient {
+            center: (0.5, 0.5),
+            radius: 1.0,
+            stops: gradient_stops(stops),
+        }
+    }
+
+    /// Sweep gradient around the middle of the widget, `stops` are (offset, color) pairs
+    pub fn sweep(angle: f32, stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        Self::SweepGradient {
+            center: (0.5, 0.5),
+            angle,
+            stops: gradient_stops(stops),
+        }
+    }
+
+    /// Move the center of a radial or sweep gradient, relative to the widget
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        match &mut self {
+            Self::RadialGradient { center, .. } | Self::SweepGradient { center, .. } => {
+                *center = (x, y);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Size of a radial gradient, 1.0 reaches the corner farthest from the center
+    pub fn radius(mut self, size: f32) -> Self {
+        if let Self::RadialGradient { radius, .. } = &mut self {
+            *radius = size.max(0.0);
+        }
+        self
+    }
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self::Color(Color::default())
+    }
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+fn gradient_stops(stops: impl IntoIterator<Item = (f32, Color)>) -> Vec<GradientStop> {
+    stops
+        .into_iter()
+        .map(|(offset, color)| GradientStop::new(offset, color))
+        .collect()
+}
@@ -0,0 +1,112 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{color::Color, hash_floats, radius::Radius};
+
+/// Width of every side of a border
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BorderWidth {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Hash for BorderWidth {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_floats(&[self.top, self.right, self.bottom, self.left], state);
+    }
+}
+
+impl BorderWidth {
+    pub fn all(width: f32) -> Self {
+        BorderWidth {
+            top: width,
+            right: width,
+            bottom: width,
+            left: width,
+        }
+    }
+
+    pub fn new() -> Self {
+        BorderWidth::all(0.0)
+    }
+
+    pub fn top(mut self, width: f32) -> Self {
+        self.top = width;
+        self
+    }
+
+    pub fn right(mut self, width: f32) -> Self {
+        self.right = width;
+        self
+    }
+
+    pub fn bottom(mut self, width: f32) -> Self {
+        self.bottom = width;
+        self
+    }
+
+    pub fn left(mut self, width: f32) -> Self {
+        self.left = width;
+        self
+    }
+
+    /// All sides have the same width
+    pub fn is_uniform(&self) -> bool {
+        self.top == self.right && self.top == self.bottom && self.top == self.left
+    }
+}
+
+impl From<u32> for BorderWidth {
+    fn from(width: u32) -> Self {
+        BorderWidth::all(width as f32)
+    }
+}
+
+impl From<f32> for BorderWidth {
+    fn from(width: f32) -> Self {
+        BorderWidth::all(width)
+    }
+}
+
+/// Line along the edge of a container, drawn inside of it
+#[derive(Debug, Clone, Default)]
+pub struct Border {
+    pub width: BorderWidth,
+    pub color: Color,
+    /// Corners of the border and the container, the container radius is used when not set
+    pub radius: Option<Radius>,
+    /// Lengths of dashes and gaps one after another, empty for a solid line
+    pub dash: Vec<f32>,
+}
+
+impl Hash for Border {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.color.hash(state);
+        self.radius.hash(state);
+        hash_floats(&self.dash, state);
+    }
+}
+
+impl Border {
+    pub fn new(width: impl Into<BorderWidth>, color: Color) -> Self {
+        Border {
+            width: width.into(),
+            color,
+            radius: None,
+            dash: Vec::new(),
+        }
+    }
+
+    pub fn radius(mut self, radius: impl Into<Radius>) -> Self {
+        self.radius = Some(radius.into());
+        self
+    }
+
+    /// Dashed line, e.g. `[6.0, 4.0]` for 6px dashes with 4px gaps
+    pub fn dash(mut self, pattern: impl Into<Vec<f32>>) -> Self {
+        self.dash = pattern.into();
+        self
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -43,6 +43,7 @@ impl Color {
         }
     }
 
+    /// Color with alpha in percent (0-100)
     pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
         let a: f32 = a as f32 / 100.0 * 255.0;
         Self {
@@ -53,6 +54,17 @@ impl Color {
         }
     }
 
+    /// Color with alpha from 0 to 255, like the other channels
+    pub fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Same color with alpha from 0.0 (transparent) to 1.0 (opaque)
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.a = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        self
+    }
+
     pub fn hex(hex: &str) -> Option<Self> {
         let hex = hex.trim_start_matches("#");
         if let Some(rgb) = convert_hex(&hex) {
@@ -0,0 +1,15 @@
+/// How an image is scaled to fit its size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Hash)]
+pub enum ContentFit {
+    /// Scale to fit inside the size, keeping aspect ratio
+    #[default]
+    Contain,
+    /// Scale to cover the whole size, keeping aspect ratio, overflow is cut off
+    Cover,
+    /// Stretch to exactly the size, aspect ratio is not kept
+    Fill,
+    /// Like `Contain`, but never scale up
+    ScaleDown,
+    /// Keep original size, overflow is cut off
+    None,
+}
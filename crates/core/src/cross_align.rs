@@ -0,0 +1,12 @@
+/// How a stack child is placed on the cross axis (horizontally in `VStack`, vertically in `HStack`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CrossAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Shrinked child takes the whole cross size of the stack
+    Stretch,
+    /// First text lines of children share the baseline, only for `HStack`
+    Baseline,
+}
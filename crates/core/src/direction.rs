@@ -0,0 +1,9 @@
+/// Direction text and rows are read in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right
+    #[default]
+    Ltr,
+    /// Right to left, for Arabic or Hebrew, mirrors rows, left/right alignment and sides
+    Rtl,
+}
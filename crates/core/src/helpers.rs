@@ -1,8 +1,14 @@
 use crate::{
     Widget,
     widget::{
-        button::Button, container::Container, image_widget::ImageWidget, label::Label,
+        button::Button,
+        container::Container,
+        image_widget::ImageWidget,
+        label::Label,
+        markdown::Markdown,
+        rich_text::{RichText, Span},
         spacer::Spacer,
+        wrap::Wrap,
     },
 };
 
@@ -10,6 +16,24 @@ pub fn label<M: Clone + Send + 'static>(content: &str) -> Label<M> {
     Label::new(content.to_string())
 }
 
+pub fn rich_text<M: Clone + Send + 'static>(spans: Vec<Span>) -> RichText<M> {
+    RichText::new(spans)
+}
+
+/// Widget that shows CommonMark text
+pub fn markdown<M: Clone + Send + 'static>(source: &str) -> Markdown<M> {
+    Markdown::new(source.to_string())
+}
+
+pub fn span(content: &str) -> Span {
+    Span::new(content.to_string())
+}
+
+/// Span that sends its url to `on_link` of the rich text when clicked
+pub fn link(content: &str, url: &str) -> Span {
+    Span::new(content.to_string()).link(url)
+}
+
 #[macro_export]
 macro_rules! vstack {
     ($($child:expr),*) => {{
@@ -26,6 +50,19 @@ macro_rules! hstack {
     }};
 }
 
+#[macro_export]
+macro_rules! wrap {
+    ($($child:expr),*) => {{
+        let children = vec![$($child),*];
+        glazeui::core::widget::wrap::Wrap::new(children)
+    }};
+}
+
+/// Children in rows that wrap when the next child doesn't fit
+pub fn flow<M: Clone + Send + 'static>(children: Vec<Widget<M>>) -> Wrap<M> {
+    Wrap::new(children)
+}
+
 pub fn container<M: Clone + Send + 'static>(child: Widget<M>) -> Container<M> {
     Container::new(child)
 }
@@ -1,11 +1,47 @@
+use std::cell::Cell;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 static NODE_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+thread_local! {
+    /// Counter of the `with_id_scope` running on this thread
+    static SCOPE_COUNTER: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
 pub fn next_id() -> u64 {
+    if let Some(id) = SCOPE_COUNTER.get() {
+        SCOPE_COUNTER.set(Some(id + 1));
+        return id;
+    }
     NODE_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
 }
 
 pub fn clear_counter() {
     NODE_ID_COUNTER.store(1, Ordering::SeqCst);
 }
+
+/// Build the widgets inside `f` with their own ids, counted from the start on this thread
+///
+/// For views laid out on their own, like headless renders running in parallel.
+/// The ids can repeat ids of widgets built outside of the scope, don't mix them in one view
+pub fn with_id_scope<T>(f: impl FnOnce() -> T) -> T {
+    let outer = SCOPE_COUNTER.replace(Some(1));
+    let result = f();
+    SCOPE_COUNTER.set(outer);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_counts_its_own_ids() {
+        let (first, second, nested) = with_id_scope(|| {
+            let first = next_id();
+            let nested = with_id_scope(next_id);
+            (first, next_id(), nested)
+        });
+        assert_eq!((first, second, nested), (1, 2, 1));
+    }
+}
@@ -0,0 +1,476 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use image::codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder};
+use image::{AnimationDecoder, DynamicImage, Frame, ImageFormat as DecodeFormat, RgbaImage};
+use peniko::{Blob, ImageBrush, ImageData, ImageFormat};
+
+use crate::widget::image_widget::Animation;
+
+/// Decoded images take at most this many bytes, least recently used images are removed first
+const CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+/// Threads that decode images in background
+const LOADER_THREADS: usize = 2;
+
+/// Decoded images by key, shared by every window and frame
+static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+/// Images waiting for a loader thread
+static LOADER: OnceLock<Sender<ImageHandle>> = OnceLock::new();
+
+/// Called when an image finished decoding in background
+static ON_LOADED: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+thread_local! {
+    /// Images are decoded on the thread that uses them instead of in background
+    static BLOCKING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn cache() -> &'static Mutex<Cache> {
+    CACHE.get_or_init(|| Mutex::new(Cache::default()))
+}
+
+/// Start the loader threads on first use, they take images from one queue
+fn loader() -> &'static Sender<ImageHandle> {
+    LOADER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<ImageHandle>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for _ in 0..LOADER_THREADS {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name("glazeui image loader".to_string())
+                .spawn(move || {
+                    loop {
+                        let Ok(handle) = receiver.lock().unwrap().recv() else {
+                            return;
+                        };
+                        handle.load_in_background();
+                    }
+                })
+                .expect("failed to start image loader thread");
+        }
+        sender
+    })
+}
+
+/// Image decoded into RGBA pixels
+#[derive(Debug)]
+pub struct DecodedImage {
+    pub image: ImageBrush,
+    /// Frames of animated images
+    pub animation: Option<Arc<Animation>>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DecodedImage {
+    /// Bytes taken by the pixels of the image and its frames
+    fn size(&self) -> usize {
+        let frames = self
+            .animation
+            .as_ref()
+            .map_or(0, |animation| animation.frames.len());
+        (1 + frames) * self.width as usize * self.height as usize * 4
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ImageState {
+    Loading,
+    Loaded(Arc<DecodedImage>),
+    /// Decoding failed, the error is kept until `retry` or `unload`
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ImageSource {
+    Path(PathBuf),
+    Bytes(Arc<[u8]>),
+    Rgba {
+        width: u32,
+        height: u32,
+        pixels: Arc<[u8]>,
+    },
+}
+
+/// Kind of the image source, images of different kinds never share a cache entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SourceKind {
+    Path,
+    Bytes,
+    Rgba,
+}
+
+impl ImageSource {
+    fn kind(&self) -> SourceKind {
+        match self {
+            ImageSource::Path(_) => SourceKind::Path,
+            ImageSource::Bytes(_) => SourceKind::Bytes,
+            ImageSource::Rgba { .. } => SourceKind::Rgba,
+        }
+    }
+}
+
+struct Entry {
+    /// Source the image was decoded from, two sources can have the same hash
+    source: ImageSource,
+    state: ImageState,
+    /// Value of the use counter when the image was used last time
+    last_used: u64,
+}
+
+/// Decoded images, removed from the least recently used when they take too much memory
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<(SourceKind, u64), Entry>,
+    /// Bytes taken by loaded images
+    size: usize,
+    /// Counts every use of an image, to find the least recently used one
+    uses: u64,
+}
+
+impl Cache {
+    /// State of the image, if it is in cache
+    fn get(&mut self, handle: &ImageHandle) -> Option<ImageState> {
+        self.uses += 1;
+        let entry = self
+            .entries
+            .get_mut(&handle.cache_key())
+            .filter(|entry| entry.source == handle.source)?;
+        entry.last_used = self.uses;
+        Some(entry.state.clone())
+    }
+
+    fn insert(&mut self, handle: &ImageHandle, state: ImageState) {
+        self.remove(handle);
+        self.uses += 1;
+        if let ImageState::Loaded(image) = &state {
+            self.size += image.size();
+        }
+        self.entries.insert(
+            handle.cache_key(),
+            Entry {
+                source: handle.source.clone(),
+                state,
+                last_used: self.uses,
+            },
+        );
+        self.evict(handle.cache_key());
+    }
+
+    fn remove(&mut self, handle: &ImageHandle) {
+        if let Some(Entry {
+            state: ImageState::Loaded(image),
+            ..
+        }) = self.entries.remove(&handle.cache_key())
+        {
+            self.size -= image.size();
+        }
+    }
+
+    /// Remove least recently used images until the cache fits in its size, `keep` stays
+    fn evict(&mut self, keep: (SourceKind, u64)) {
+        while self.size > CACHE_SIZE {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(key, entry)| {
+                    **key != keep && matches!(entry.state, ImageState::Loaded(_))
+                })
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+            let Some(oldest) = oldest else {
+                return;
+            };
+            if let Some(Entry {
+                state: ImageState::Loaded(image),
+                ..
+            }) = self.entries.remove(&oldest)
+            {
+                self.size -= image.size();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.size = 0;
+    }
+}
+
+/// Image that is decoded once outside of the UI thread and cached by its key
+///
+/// Create the handle once (for example in app struct) and give it to `image().handle()`,
+/// creating it in the view hashes the whole image every frame
+#[derive(Debug, Clone)]
+pub struct ImageHandle {
+    key: u64,
+    source: ImageSource,
+}
+
+impl ImageHandle {
+    /// Image file, the key is the path
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            key: key_of(&path),
+            source: ImageSource::Path(path),
+        }
+    }
+
+    /// Encoded image (PNG, JPEG, GIF, ...), the key is hash of the bytes
+    ///
+    /// Give an `Arc<[u8]>` that is kept between frames so the bytes aren't copied
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Self {
+        let bytes = bytes.into();
+        Self {
+            key: key_of(&bytes),
+            source: ImageSource::Bytes(bytes),
+        }
+    }
+
+    /// Raw RGBA8 pixels, the key is hash of the pixels
+    pub fn from_rgba(width: u32, height: u32, pixels: impl Into<Arc<[u8]>>) -> Self {
+        let pixels = pixels.into();
+        Self {
+            key: key_of(&(width, height, &pixels)),
+            source: ImageSource::Rgba {
+                width,
+                height,
+                pixels,
+            },
+        }
+    }
+
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    fn cache_key(&self) -> (SourceKind, u64) {
+        (self.source.kind(), self.key)
+    }
+
+    /// State of the image, starts decoding in background if it wasn't started yet
+    pub fn load(&self) -> ImageState {
+        if BLOCKING.get() {
+            return match self.load_blocking() {
+                Ok(image) => ImageState::Loaded(image),
+                Err(e) => ImageState::Failed(e.to_string()),
+            };
+        }
+
+        let mut images = cache().lock().unwrap();
+        if let Some(state) = images.get(self) {
+            return state;
+        }
+        images.insert(self, ImageState::Loading);
+        drop(images);
+
+        loader().send(self.clone()).ok();
+        ImageState::Loading
+    }
+
+    /// Decode the image on a loader thread and tell the shell to redraw
+    fn load_in_background(&self) {
+        let state = match self.decode() {
+            Ok(image) => ImageState::Loaded(Arc::new(image)),
+            Err(e) => ImageState::Failed(e.to_string()),
+        };
+
+        let mut images = cache().lock().unwrap();
+        // Image was unloaded while it was decoded
+        if !matches!(images.get(self), Some(ImageState::Loading)) {
+            return;
+        }
+        images.insert(self, state);
+        drop(images);
+
+        if let Some(on_loaded) = ON_LOADED.get() {
+            on_loaded();
+        }
+    }
+
+    /// Get the image, decoding it on this thread if it isn't in cache
+    pub fn load_blocking(&self) -> std::io::Result<Arc<DecodedImage>> {
+        if let Some(ImageState::Loaded(image)) = cache().lock().unwrap().get(self) {
+            return Ok(image);
+        }
+
+        let image = Arc::new(self.decode()?);
+        cache()
+            .lock()
+            .unwrap()
+            .insert(self, ImageState::Loaded(image.clone()));
+        Ok(image)
+    }
+
+    /// Why the image couldn't be decoded, `None` while it loads or when it is loaded
+    pub fn error(&self) -> Option<String> {
+        match cache().lock().unwrap().get(self) {
+            Some(ImageState::Failed(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Decode the image again if it failed, for example after the file was fixed
+    pub fn retry(&self) {
+        let mut images = cache().lock().unwrap();
+        if let Some(ImageState::Failed(_)) = images.get(self) {
+            images.remove(self);
+        }
+    }
+
+    /// Remove the image from cache, it will be decoded again when used
+    pub fn unload(&self) {
+        cache().lock().unwrap().remove(self);
+    }
+
+    fn decode(&self) -> std::io::Result<DecodedImage> {
+        match &self.source {
+            ImageSource::Path(path) => decode(&std::fs::read(path)?),
+            ImageSource::Bytes(bytes) => decode(bytes),
+            ImageSource::Rgba {
+                width,
+                height,
+                pixels,
+            } => {
+                let image =
+                    RgbaImage::from_raw(*width, *height, pixels.to_vec()).ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "pixels don't match image size",
+                        )
+                    })?;
+                Ok(DecodedImage {
+                    image: image_data(image).into(),
+                    animation: None,
+                    width: *width,
+                    height: *height,
+                })
+            }
+        }
+    }
+}
+
+/// Set the function called when a background decode finishes, used by the shell to redraw
+pub fn on_image_loaded(callback: impl Fn() + Send + Sync + 'static) {
+    ON_LOADED.set(Box::new(callback)).ok();
+}
+
+/// Decode the images used inside `f` on this thread before `load` returns
+///
+/// Used when there is only one frame to draw, like in headless rendering
+pub fn load_blocking_in<T>(f: impl FnOnce() -> T) -> T {
+    let blocking = BLOCKING.replace(true);
+    let result = f();
+    BLOCKING.set(blocking);
+    result
+}
+
+/// Remove all images from cache
+pub fn clear_image_cache() {
+    cache().lock().unwrap().clear();
+}
+
+fn key_of(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Decode the image and every frame if it is animated
+pub fn decode(data: &[u8]) -> std::io::Result<DecodedImage> {
+    let reader = image::ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+
+    let frames = match reader.format() {
+        Some(format) => decode_frames(data, format).map_err(invalid_data)?,
+        None => Vec::new(),
+    };
+
+    let image = if let Some(frame) = frames.first() {
+        DynamicImage::ImageRgba8(frame.buffer().clone())
+    } else {
+        reader.decode().map_err(invalid_data)?
+    };
+
+    let (width, height) = (image.width(), image.height());
+
+    let animation = if frames.len() > 1 {
+        let frames = frames
+            .into_iter()
+            .map(|frame| {
+                let (numer, denom) = frame.delay().numer_denom_ms();
+                let mut delay = Duration::from_micros(numer as u64 * 1000 / denom.max(1) as u64);
+                // Browsers show frames without delay for 100ms, do the same
+                if delay < Duration::from_millis(10) {
+                    delay = Duration::from_millis(100);
+                }
+                let data = image_data(frame.into_buffer());
+                (ImageBrush::from(data), delay)
+            })
+            .collect();
+        Some(Arc::new(Animation::new(frames)))
+    } else {
+        None
+    };
+
+    Ok(DecodedImage {
+        image: image_data(image.into_rgba8()).into(),
+        animation,
+        width,
+        height,
+    })
+}
+
+fn invalid_data(error: image::ImageError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
+
+/// Decode all frames of animated formats, still images return no frames
+fn decode_frames(data: &[u8], format: DecodeFormat) -> image::ImageResult<Vec<Frame>> {
+    match format {
+        DecodeFormat::Gif => GifDecoder::new(Cursor::new(data))?
+            .into_frames()
+            .collect_frames(),
+        DecodeFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(data))?;
+            if decoder.is_apng()? {
+                decoder.apng()?.into_frames().collect_frames()
+            } else {
+                Ok(Vec::new())
+            }
+        }
+        DecodeFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(data))?;
+            if decoder.has_animation() {
+                decoder.into_frames().collect_frames()
+            } else {
+                Ok(Vec::new())
+            }
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Turn RGBA image into image data
+fn image_data(image: RgbaImage) -> ImageData {
+    let (width, height) = image.dimensions();
+    let data = Arc::new(image.into_vec());
+    let blob = Blob::new(data);
+
+    ImageData {
+        data: blob,
+        format: ImageFormat::Rgba8,
+        width,
+        height,
+        alpha_type: peniko::ImageAlphaType::Alpha,
+    }
+}
@@ -0,0 +1,14 @@
+/// How a stack places its children along its main axis when there is space left
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// First child at the start, last at the end, same space between the others
+    SpaceBetween,
+    /// Same space on both sides of each child
+    SpaceAround,
+    /// Same space between children and the stack edges
+    SpaceEvenly,
+}
@@ -0,0 +1,38 @@
+/// Size of a widget along one axis
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Length {
+    /// Exact size in pixels
+    Fixed(f32),
+    /// As small as the content
+    #[default]
+    Shrink,
+    /// All available space, shared equally with other filling siblings
+    Fill,
+    /// Share of available space, relative to the portions of filling siblings
+    FillPortion(u16),
+    /// Percent (0-100) of available space
+    Percent(f32),
+}
+
+impl Length {
+    /// Portion of the space left in a stack, `None` if the length doesn't fill
+    pub fn fill_portion(&self) -> Option<u16> {
+        match self {
+            Length::Fill => Some(1),
+            Length::FillPortion(portion) => Some(*portion),
+            _ => None,
+        }
+    }
+}
+
+impl From<u32> for Length {
+    fn from(size: u32) -> Self {
+        Length::Fixed(size as f32)
+    }
+}
+
+impl From<f32> for Length {
+    fn from(size: f32) -> Self {
+        Length::Fixed(size)
+    }
+}
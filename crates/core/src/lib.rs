@@ -1,31 +1,60 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use crate::id::next_id;
 use crate::style::Style;
 mod align;
 mod backend;
+mod background;
+mod border;
 mod color;
+mod content_fit;
+mod cross_align;
+mod direction;
 pub mod futures;
 mod helpers;
 pub mod id;
+pub mod image_handle;
+mod justify;
+mod length;
 mod margin;
 mod padding;
+mod position;
+mod radius;
+mod rect;
+mod shadow;
 pub mod style;
 mod text_style;
+mod transform;
 mod weight;
 pub mod widget;
 pub mod window;
 
 pub use align::*;
 pub use backend::*;
+pub use background::*;
+pub use border::*;
 pub use color::*;
+pub use content_fit::*;
+pub use cross_align::*;
+pub use direction::*;
 pub use futures::*;
 pub use helpers::*;
+pub use justify::*;
+pub use length::*;
 pub use margin::*;
 pub use padding::*;
 use peniko::ImageBrush;
+pub use position::*;
+pub use radius::*;
+pub use rect::*;
+pub use shadow::*;
 pub use text_style::*;
+pub use transform::*;
 pub use weight::*;
+use widget::image_widget::Animation;
+use widget::rich_text::{OnLink, TextSpan};
 
 /// Widget with a generic Message type
 pub struct Widget<M: Clone + Send + 'static> {
@@ -43,6 +72,111 @@ pub struct Widget<M: Clone + Send + 'static> {
     pub style: Style,
 }
 
+impl<M: Clone + Send + 'static> Widget<M> {
+    /// Cross axis alignment of the widget inside a stack, instead of the stack one
+    pub fn align_self(mut self, align: CrossAlign) -> Self {
+        self.style.align_self = Some(align);
+        self
+    }
+
+    /// Direction of the widget and its children, instead of the direction of its parent
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.style.direction = Some(direction);
+        self
+    }
+
+    /// Fade the widget with its children as one image, 0.0 is invisible and 1.0 opaque
+    ///
+    /// Overlapping children don't show through each other like with transparent colors
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.style.opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Rotate the drawn widget and its children clockwise around its center, in degrees
+    pub fn rotate(mut self, degrees: f32) -> Self {
+        self.style
+            .transform
+            .get_or_insert_with(Transform::default)
+            .rotate = degrees;
+        self
+    }
+
+    /// Scale the drawn widget and its children from its center
+    pub fn scale(mut self, x: f32, y: f32) -> Self {
+        self.style
+            .transform
+            .get_or_insert_with(Transform::default)
+            .scale = (x, y);
+        self
+    }
+
+    /// Move the drawn widget and its children without changing the layout
+    pub fn translate(mut self, x: f32, y: f32) -> Self {
+        self.style
+            .transform
+            .get_or_insert_with(Transform::default)
+            .translate = (x, y);
+        self
+    }
+
+    /// Place the widget relative to its parent box, outside of the parent layout
+    pub fn position(mut self, position: impl Into<Position>) -> Self {
+        self.style.position = position.into();
+        self
+    }
+
+    /// Take part of the space left in a stack, relative to the grow of other children
+    ///
+    /// Only a stack with a fixed or filling length has space left, a shrinked stack fits its children
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.style.grow = grow.max(0.0);
+        self
+    }
+
+    /// Give back part of the stack overflow, relative to the shrink and size of other children
+    ///
+    /// Unlike CSS, where `flex-shrink` is 1, children don't shrink by default (0.0),
+    /// set it to let a child get smaller than its content when the stack overflows
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.style.shrink = shrink.max(0.0);
+        self
+    }
+
+    /// Smallest width the layout can give the widget
+    pub fn min_width(mut self, width: u32) -> Self {
+        self.style.min_width = Some(width);
+        self
+    }
+
+    /// Largest width the layout can give the widget, longer text wraps
+    pub fn max_width(mut self, width: u32) -> Self {
+        self.style.max_width = Some(width);
+        self
+    }
+
+    /// Smallest height the layout can give the widget
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.style.min_height = Some(height);
+        self
+    }
+
+    /// Largest height the layout can give the widget
+    pub fn max_height(mut self, height: u32) -> Self {
+        self.style.max_height = Some(height);
+        self
+    }
+}
+
+/// Hash floats by their bits, for style types that are compared between frames
+///
+/// `-0.0` is hashed like `0.0`, because they are equal
+fn hash_floats<H: Hasher>(values: &[f32], state: &mut H) {
+    for value in values {
+        (value + 0.0).to_bits().hash(state);
+    }
+}
+
 impl<M: Clone + Send + 'static> fmt::Debug for Widget<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Widget")
@@ -68,8 +202,12 @@ pub enum WidgetElement<M: Clone + Send + 'static> {
     /// A Rectangle that holds a child
     Container {
         child: Box<Widget<M>>,
-        color: (u8, u8, u8, u8),
-        radius: u32,
+        background: Background,
+        radius: Radius,
+        border: Option<Border>,
+        shadows: Vec<Shadow>,
+        /// Child is masked to the rounded rectangle of the container
+        clip: bool,
     },
 
     Label {
@@ -80,18 +218,34 @@ pub enum WidgetElement<M: Clone + Send + 'static> {
         color: (u8, u8, u8, u8),
     },
 
+    /// Text made of differently styled spans, some of which can be links
+    RichText {
+        spans: Vec<TextSpan>,
+        link_hover_color: (u8, u8, u8, u8),
+        on_link: Option<OnLink<M>>,
+    },
+
     Image {
         image: ImageBrush,
+        /// Frames of animated images
+        animation: Option<Arc<Animation>>,
+        fit: ContentFit,
+        /// Part of the image that is shown, in image pixels
+        crop: Option<Rect>,
+        opacity: f32,
+        tint: Option<(u8, u8, u8, u8)>,
     },
 
     /// Vertical list
-    VStack {
-        children: Vec<Widget<M>>,
-    },
+    VStack { children: Vec<Widget<M>> },
 
     /// Horizontal list
-    HStack {
+    HStack { children: Vec<Widget<M>> },
+
+    /// Rows of children that wrap to the available width
+    Wrap {
         children: Vec<Widget<M>>,
+        vertical_spacing: i32,
     },
 
     /// Empty space
@@ -104,13 +258,19 @@ impl<M: Clone + Send + 'static> fmt::Debug for WidgetElement<M> {
         match self {
             WidgetElement::Container {
                 child,
-                color,
+                background,
                 radius,
+                border,
+                shadows,
+                clip,
             } => f
                 .debug_struct("Container")
                 .field("child", child)
-                .field("color", color)
+                .field("background", background)
                 .field("radius", radius)
+                .field("border", border)
+                .field("shadows", shadows)
+                .field("clip", clip)
                 .finish(),
             WidgetElement::Label {
                 content,
@@ -126,9 +286,32 @@ impl<M: Clone + Send + 'static> fmt::Debug for WidgetElement<M> {
                 .field("style", style)
                 .field("color", color)
                 .finish(),
-            WidgetElement::Image { image } => {
-                f.debug_struct("image").field("image", image).finish()
-            }
+            WidgetElement::RichText {
+                spans,
+                link_hover_color,
+                on_link,
+            } => f
+                .debug_struct("RichText")
+                .field("spans", spans)
+                .field("link_hover_color", link_hover_color)
+                .field("on_link", &on_link.is_some())
+                .finish(),
+            WidgetElement::Image {
+                image,
+                animation,
+                fit,
+                crop,
+                opacity,
+                tint,
+            } => f
+                .debug_struct("image")
+                .field("image", image)
+                .field("animation", animation)
+                .field("fit", fit)
+                .field("crop", crop)
+                .field("opacity", opacity)
+                .field("tint", tint)
+                .finish(),
             WidgetElement::VStack { children } => f
                 .debug_struct("VStack")
                 .field("children", children)
@@ -137,6 +320,14 @@ impl<M: Clone + Send + 'static> fmt::Debug for WidgetElement<M> {
                 .debug_struct("HStack")
                 .field("children", children)
                 .finish(),
+            WidgetElement::Wrap {
+                children,
+                vertical_spacing,
+            } => f
+                .debug_struct("Wrap")
+                .field("children", children)
+                .field("vertical_spacing", vertical_spacing)
+                .finish(),
             WidgetElement::Spacer {} => f.debug_struct("Spacer").finish(),
         }
     }
@@ -145,8 +336,20 @@ impl<M: Clone + Send + 'static> fmt::Debug for WidgetElement<M> {
 impl<M: Clone + Send + 'static> Clone for WidgetElement<M> {
     fn clone(&self) -> Self {
         match self {
-            WidgetElement::Image { image } => WidgetElement::Image {
+            WidgetElement::Image {
+                image,
+                animation,
+                fit,
+                crop,
+                opacity,
+                tint,
+            } => WidgetElement::Image {
                 image: image.clone(),
+                animation: animation.clone(),
+                fit: *fit,
+                crop: *crop,
+                opacity: *opacity,
+                tint: *tint,
             },
             WidgetElement::Label {
                 content,
@@ -161,14 +364,29 @@ impl<M: Clone + Send + 'static> Clone for WidgetElement<M> {
                 style: style.clone(),
                 color: *color,
             },
+            WidgetElement::RichText {
+                spans,
+                link_hover_color,
+                on_link,
+            } => WidgetElement::RichText {
+                spans: spans.clone(),
+                link_hover_color: *link_hover_color,
+                on_link: on_link.clone(),
+            },
             WidgetElement::Container {
                 child,
-                color,
+                background,
                 radius,
+                border,
+                shadows,
+                clip,
             } => WidgetElement::Container {
                 child: Box::new((**child).clone()),
-                color: *color,
+                background: background.clone(),
                 radius: *radius,
+                border: border.clone(),
+                shadows: shadows.clone(),
+                clip: *clip,
             },
             WidgetElement::VStack { children } => WidgetElement::VStack {
                 children: children.iter().map(|c| c.clone()).collect(),
@@ -176,6 +394,13 @@ impl<M: Clone + Send + 'static> Clone for WidgetElement<M> {
             WidgetElement::HStack { children } => WidgetElement::HStack {
                 children: children.iter().map(|c| c.clone()).collect(),
             },
+            WidgetElement::Wrap {
+                children,
+                vertical_spacing,
+            } => WidgetElement::Wrap {
+                children: children.iter().map(|c| c.clone()).collect(),
+                vertical_spacing: *vertical_spacing,
+            },
             WidgetElement::Spacer {} => WidgetElement::Spacer {},
         }
     }
@@ -42,7 +42,7 @@ impl Margin {
     }
 
     pub fn top(mut self, margin: i32) -> Self {
-        self.bottom = margin;
+        self.top = margin;
         self
     }
 }
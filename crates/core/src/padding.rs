@@ -1,3 +1,5 @@
+use crate::border::Border;
+
 #[derive(Debug, Default, Clone)]
 /// Space inside an element
 pub struct Padding {
@@ -42,7 +44,18 @@ impl Padding {
     }
 
     pub fn top(mut self, padding: i32) -> Self {
-        self.bottom = padding;
+        self.top = padding;
+        self
+    }
+
+    /// Padding with the border widths added, so the child isn't drawn under the border
+    pub(crate) fn around_border(mut self, border: Option<&Border>) -> Self {
+        if let Some(border) = border {
+            self.top += border.width.top.ceil() as i32;
+            self.left += border.width.left.ceil() as i32;
+            self.right += border.width.right.ceil() as i32;
+            self.bottom += border.width.bottom.ceil() as i32;
+        }
         self
     }
 }
@@ -0,0 +1,28 @@
+/// How a widget is placed by its parent
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Position {
+    /// Placed by the parent layout (stack flow, container align)
+    #[default]
+    Static,
+    /// Placed relative to the parent box and left out of the parent layout
+    Absolute(Absolute),
+}
+
+/// Distances from the edges of the parent box, unset edges are free
+///
+/// When both `left` and `right` (or `top` and `bottom`) are set, a shrinked widget
+/// is stretched between them. In right to left direction `left` and `right` are swapped,
+/// so `left` is always the distance from the edge where rows start
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Absolute {
+    pub left: Option<f32>,
+    pub top: Option<f32>,
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+}
+
+impl From<Absolute> for Position {
+    fn from(absolute: Absolute) -> Self {
+        Position::Absolute(absolute)
+    }
+}
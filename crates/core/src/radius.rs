@@ -0,0 +1,73 @@
+use std::hash::{Hash, Hasher};
+
+use crate::hash_floats;
+
+/// Radius of every corner of a rounded rectangle
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Radius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl Hash for Radius {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_floats(
+            &[
+                self.top_left,
+                self.top_right,
+                self.bottom_right,
+                self.bottom_left,
+            ],
+            state,
+        );
+    }
+}
+
+impl Radius {
+    pub fn all(radius: f32) -> Self {
+        Radius {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    pub fn new() -> Self {
+        Radius::all(0.0)
+    }
+
+    pub fn top_left(mut self, radius: f32) -> Self {
+        self.top_left = radius;
+        self
+    }
+
+    pub fn top_right(mut self, radius: f32) -> Self {
+        self.top_right = radius;
+        self
+    }
+
+    pub fn bottom_right(mut self, radius: f32) -> Self {
+        self.bottom_right = radius;
+        self
+    }
+
+    pub fn bottom_left(mut self, radius: f32) -> Self {
+        self.bottom_left = radius;
+        self
+    }
+}
+
+impl From<u32> for Radius {
+    fn from(radius: u32) -> Self {
+        Radius::all(radius as f32)
+    }
+}
+
+impl From<f32> for Radius {
+    fn from(radius: f32) -> Self {
+        Radius::all(radius)
+    }
+}
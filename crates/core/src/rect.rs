@@ -0,0 +1,39 @@
+use std::hash::{Hash, Hasher};
+
+use crate::hash_floats;
+
+/// Rectangle with position of top left corner and size
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Hash for Rect {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_floats(&[self.x, self.y, self.width, self.height], state);
+    }
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Size of the part of the rectangle that is inside of a `width` x `height` area at (0, 0)
+    ///
+    /// Used for the shown part of a cropped image, a crop outside of the image shows nothing
+    pub fn size_inside(&self, width: f32, height: f32) -> (f32, f32) {
+        (
+            ((self.x + self.width).min(width) - self.x.max(0.0)).max(0.0),
+            ((self.y + self.height).min(height) - self.y.max(0.0)).max(0.0),
+        )
+    }
+}
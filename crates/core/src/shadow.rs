@@ -0,0 +1,51 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{color::Color, hash_floats};
+
+/// Blurred copy of the widget shape drawn under it, or inside of it when `inset`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shadow {
+    /// Horizontal and vertical distance from the widget
+    pub offset: (f32, f32),
+    /// How soft the edge is, 0 is a sharp edge
+    pub blur_radius: f32,
+    /// Grows the shadow shape in every direction, negative shrinks it
+    pub spread: f32,
+    pub color: Color,
+    /// Drawn inside the widget edge instead of outside
+    pub inset: bool,
+}
+
+impl Hash for Shadow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_floats(
+            &[self.offset.0, self.offset.1, self.blur_radius, self.spread],
+            state,
+        );
+        self.color.hash(state);
+        self.inset.hash(state);
+    }
+}
+
+impl Shadow {
+    pub fn new(offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
+        Shadow {
+            offset: (offset_x, offset_y),
+            blur_radius: blur_radius.max(0.0),
+            spread: 0.0,
+            color,
+            inset: false,
+        }
+    }
+
+    pub fn spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Shadow inside the widget, like a pressed button or an input field
+    pub fn inset(mut self) -> Self {
+        self.inset = true;
+        self
+    }
+}
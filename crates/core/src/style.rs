@@ -1,11 +1,38 @@
-use crate::{align::Align, margin::Margin, padding::Padding};
+use crate::{
+    align::Align, cross_align::CrossAlign, direction::Direction, justify::Justify, length::Length,
+    margin::Margin, padding::Padding, position::Position, transform::Transform,
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct Style {
-    pub width: u32,
-    pub height: u32,
+    pub width: Length,
+    pub height: Length,
+    pub min_width: Option<u32>,
+    pub max_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub max_height: Option<u32>,
+    /// Width divided by height, used for the size that isn't set
+    pub aspect_ratio: Option<f32>,
     pub padding: Padding,
     pub margin: Margin,
     pub spacing: i32,
     pub align: Option<Align>,
+    /// Placement of stack children on the main axis
+    pub justify: Justify,
+    /// Cross axis alignment of stack children
+    pub cross_align: CrossAlign,
+    /// Cross axis alignment of this widget inside a stack
+    pub align_self: Option<CrossAlign>,
+    /// Share of the space left in a stack this widget takes, 0 keeps its size
+    pub grow: f32,
+    /// Share of the stack overflow this widget gives back, 0 keeps its size
+    pub shrink: f32,
+    /// Placement by the parent layout or relative to the parent box
+    pub position: Position,
+    /// Direction of the subtree, inherited from the parent when not set
+    pub direction: Option<Direction>,
+    /// Opacity (0.0-1.0) of the widget and its children composited together, `None` is opaque
+    pub opacity: Option<f32>,
+    /// Move, rotation and scale applied when drawing, also to pointer hits
+    pub transform: Option<Transform>,
 }
@@ -1,7 +1,9 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TextStyle {
     Italic,
     Normal,
     Underline,
     Striketrough,
+    /// Italic and struck through, for emphasis inside struck text
+    ItalicStriketrough,
 }
@@ -0,0 +1,22 @@
+/// Move, rotation and scale of a drawn widget, around its center
+///
+/// Layout doesn't see the transform, other widgets keep their place
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// Move in pixels
+    pub translate: (f32, f32),
+    /// Clockwise rotation in degrees
+    pub rotate: f32,
+    /// Horizontal and vertical scale, 1.0 keeps the size
+    pub scale: (f32, f32),
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translate: (0.0, 0.0),
+            rotate: 0.0,
+            scale: (1.0, 1.0),
+        }
+    }
+}
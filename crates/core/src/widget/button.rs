@@ -1,6 +1,6 @@
 use crate::{
-    Margin, Padding, TextStyle, Widget, align::Align, color::Color, id::next_id, style::Style,
-    weight::TextWeight,
+    Background, Border, Length, Margin, Padding, Radius, Shadow, TextStyle, Widget, align::Align,
+    color::Color, id::next_id, style::Style, weight::TextWeight,
 };
 
 pub struct Button<M: Clone + Send + 'static> {
@@ -10,10 +10,12 @@ pub struct Button<M: Clone + Send + 'static> {
     pub label_color: Color,
     pub label_style: TextStyle,
     pub label_spacing: i32,
-    pub width: u32,
-    pub height: u32,
-    pub color: Color,
-    pub radius: u32,
+    pub width: Length,
+    pub height: Length,
+    pub background: Background,
+    pub radius: Radius,
+    pub border: Option<Border>,
+    pub shadows: Vec<Shadow>,
     pub on_press: Option<M>,
     pub label_on_press: Option<M>,
     pub margin: Margin,
@@ -29,10 +31,12 @@ impl<M: Clone + Send + 'static> Button<M> {
             label_color: Color::rgb(255, 255, 255),
             label_style: TextStyle::Normal,
             label_spacing: 0,
-            width: 100,
-            height: 50,
-            color: Color::rgb(50, 50, 51),
-            radius: 0,
+            width: Length::Fixed(100.0),
+            height: Length::Fixed(50.0),
+            background: Background::Color(Color::rgb(50, 50, 51)),
+            radius: Radius::new(),
+            border: None,
+            shadows: Vec::new(),
             on_press: None,
             label_on_press: None,
             margin: Margin::new(),
@@ -50,13 +54,13 @@ impl<M: Clone + Send + 'static> Button<M> {
         self
     }
 
-    pub fn width(mut self, width: u32) -> Self {
-        self.width = width;
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
         self
     }
 
-    pub fn height(mut self, height: u32) -> Self {
-        self.height = height;
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
         self
     }
 
@@ -92,12 +96,31 @@ impl<M: Clone + Send + 'static> Button<M> {
     }
 
     pub fn color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.background = Background::Color(color);
         self
     }
 
-    pub fn radius(mut self, corner_radius: u32) -> Self {
-        self.radius = corner_radius;
+    /// Solid color or gradient behind the content
+    pub fn background(mut self, background: impl Into<Background>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Radius of the corners, a number for all of them or a `Radius` for each one
+    pub fn radius(mut self, corner_radius: impl Into<Radius>) -> Self {
+        self.radius = corner_radius.into();
+        self
+    }
+
+    /// Line along the edge, it pushes the content in like padding
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Add a shadow, shadows are drawn in the order they are added
+    pub fn shadow(mut self, shadow: Shadow) -> Self {
+        self.shadows.push(shadow);
         self
     }
 
@@ -107,8 +130,13 @@ impl<M: Clone + Send + 'static> Button<M> {
     }
 
     pub fn build(self) -> Widget<M> {
-        // Button color
-        let (r, g, b, a) = (self.color.r, self.color.g, self.color.b, self.color.a);
+        // Border can have its own corners
+        let radius = self
+            .border
+            .as_ref()
+            .and_then(|border| border.radius)
+            .unwrap_or(self.radius);
+
         // Text color
         let (r2, g2, b2, a2) = (
             self.label_color.a,
@@ -142,7 +170,7 @@ impl<M: Clone + Send + 'static> Button<M> {
         let container_style = Style {
             width: self.width,
             height: self.height,
-            padding: self.padding,
+            padding: self.padding.around_border(self.border.as_ref()),
             margin: self.margin,
             ..Default::default()
         };
@@ -152,8 +180,11 @@ impl<M: Clone + Send + 'static> Button<M> {
             id: next_id(),
             element: crate::WidgetElement::Container {
                 child: Box::new(child),
-                color: (r, g, b, a),
-                radius: self.radius,
+                background: self.background,
+                radius,
+                border: self.border,
+                shadows: self.shadows,
+                clip: false,
             },
             on_press: self.on_press,
             style: container_style,
@@ -1,27 +1,39 @@
-use crate::{Margin, Padding, Widget, color::Color, id::next_id, style::Style};
+use crate::{
+    Background, Border, Length, Margin, Padding, Radius, Shadow, Widget, color::Color, id::next_id,
+    style::Style,
+};
 
 pub struct Container<M: Clone + Send + 'static> {
     pub child: Widget<M>,
-    pub width: u32,
-    pub height: u32,
-    pub color: Color,
-    pub radius: u32,
+    pub width: Length,
+    pub height: Length,
+    pub background: Background,
+    pub radius: Radius,
+    pub border: Option<Border>,
+    pub shadows: Vec<Shadow>,
+    pub clip: bool,
     pub on_press: Option<M>,
     pub margin: Margin,
     pub padding: Padding,
+    pub aspect_ratio: Option<f32>,
 }
 
 impl<M: Clone + Send + 'static> Container<M> {
+    /// Container of 100x50, `Length::Shrink` sizes take the size of the child with padding
     pub fn new(child: Widget<M>) -> Self {
         Self {
             child,
-            width: 100,
-            height: 50,
-            color: Color::rgb(50, 50, 51),
-            radius: 0,
+            width: Length::Fixed(100.0),
+            height: Length::Fixed(50.0),
+            background: Background::Color(Color::rgb(50, 50, 51)),
+            radius: Radius::new(),
+            border: None,
+            shadows: Vec::new(),
+            clip: false,
             on_press: None,
             margin: Margin::new(),
             padding: Padding::new(),
+            aspect_ratio: None,
         }
     }
 
@@ -35,23 +47,54 @@ impl<M: Clone + Send + 'static> Container<M> {
         self
     }
 
-    pub fn width(mut self, width: u32) -> Self {
-        self.width = width;
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
         self
     }
 
-    pub fn height(mut self, height: u32) -> Self {
-        self.height = height;
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Width divided by height, the size that isn't set is derived from it
+    pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = Some(ratio);
         self
     }
 
     pub fn color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.background = Background::Color(color);
+        self
+    }
+
+    /// Solid color or gradient behind the content
+    pub fn background(mut self, background: impl Into<Background>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Radius of the corners, a number for all of them or a `Radius` for each one
+    pub fn radius(mut self, corner_radius: impl Into<Radius>) -> Self {
+        self.radius = corner_radius.into();
+        self
+    }
+
+    /// Line along the edge, it pushes the content in like padding
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Add a shadow, shadows are drawn in the order they are added
+    pub fn shadow(mut self, shadow: Shadow) -> Self {
+        self.shadows.push(shadow);
         self
     }
 
-    pub fn radius(mut self, corner_radius: u32) -> Self {
-        self.radius = corner_radius;
+    /// Hide the parts of the child outside of the container, following its rounded corners
+    pub fn clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
         self
     }
 
@@ -61,13 +104,19 @@ impl<M: Clone + Send + 'static> Container<M> {
     }
 
     pub fn build(self) -> Widget<M> {
-        let (r, g, b, a) = (self.color.r, self.color.g, self.color.b, self.color.a);
+        // Border can have its own corners
+        let radius = self
+            .border
+            .as_ref()
+            .and_then(|border| border.radius)
+            .unwrap_or(self.radius);
 
         // Container style
         let container_style = Style {
             width: self.width,
             height: self.height,
-            padding: self.padding,
+            padding: self.padding.around_border(self.border.as_ref()),
+            aspect_ratio: self.aspect_ratio,
             margin: self.margin,
             ..Default::default()
         };
@@ -76,8 +125,11 @@ impl<M: Clone + Send + 'static> Container<M> {
             id: next_id(),
             element: crate::WidgetElement::Container {
                 child: Box::new(self.child),
-                color: (r, g, b, a),
-                radius: self.radius,
+                background: self.background,
+                radius,
+                border: self.border,
+                shadows: self.shadows,
+                clip: self.clip,
             },
             on_press: self.on_press,
             style: container_style,
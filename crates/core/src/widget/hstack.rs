@@ -1,9 +1,13 @@
-use crate::{Margin, Widget, id::next_id, style::Style};
+use crate::{CrossAlign, Justify, Length, Margin, Widget, id::next_id, style::Style};
 
 #[derive(Debug)]
 pub struct HStack<M: Clone + Send + 'static> {
     pub children: Vec<Widget<M>>,
     pub spacing: i32,
+    pub justify: Justify,
+    pub cross_align: CrossAlign,
+    pub width: Length,
+    pub height: Length,
     pub margin: Margin,
 }
 
@@ -12,6 +16,10 @@ impl<M: Clone + Send + 'static> HStack<M> {
         Self {
             children,
             spacing: 0,
+            justify: Justify::Start,
+            cross_align: CrossAlign::Start,
+            width: Length::Shrink,
+            height: Length::Shrink,
             margin: Margin::new(),
         }
     }
@@ -34,11 +42,37 @@ impl<M: Clone + Send + 'static> HStack<M> {
         self
     }
 
+    /// How children are placed horizontally when the stack is bigger than them
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// How children are aligned vertically, a child can override it with `align_self`
+    pub fn cross_align(mut self, align: CrossAlign) -> Self {
+        self.cross_align = align;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
     pub fn build(self) -> Widget<M> {
         // HStack style
         let hstack_style = Style {
             spacing: self.spacing,
+            justify: self.justify,
+            cross_align: self.cross_align,
             margin: self.margin,
+            width: self.width,
+            height: self.height,
             ..Default::default()
         };
 
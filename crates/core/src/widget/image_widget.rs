@@ -1,18 +1,76 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 use std::{marker::PhantomData, path::Path};
 
-use image::imageops::FilterType;
-use peniko::{Blob, ImageBrush, ImageData, ImageFormat};
+use peniko::ImageBrush;
 
 use crate::id::next_id;
+use crate::image_handle::{DecodedImage, ImageHandle, ImageState};
 use crate::style::Style;
-use crate::{Margin, Widget};
+use crate::{Color, ContentFit, Length, Margin, Rect, Widget};
+
+/// Time from which all animations are played, so rebuilt widgets keep their frame
+static ANIMATION_EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// Frames of an animated image (GIF, APNG, WebP) with the time each frame is shown
+#[derive(Debug)]
+pub struct Animation {
+    pub frames: Vec<(ImageBrush, Duration)>,
+    pub duration: Duration,
+}
+
+impl Animation {
+    pub fn new(frames: Vec<(ImageBrush, Duration)>) -> Self {
+        let duration = frames.iter().map(|(_, delay)| *delay).sum();
+        Self { frames, duration }
+    }
+
+    /// Index of frame shown at the time and time left until the next frame
+    fn position(&self, time: Instant) -> (usize, Duration) {
+        let epoch = *ANIMATION_EPOCH.get_or_init(Instant::now);
+        if self.duration.is_zero() {
+            return (0, Duration::MAX);
+        }
+
+        let elapsed = time.saturating_duration_since(epoch).as_nanos() % self.duration.as_nanos();
+        let mut elapsed = Duration::from_nanos(elapsed as u64);
+
+        for (index, (_, delay)) in self.frames.iter().enumerate() {
+            if elapsed < *delay {
+                return (index, *delay - elapsed);
+            }
+            elapsed -= *delay;
+        }
+        (0, self.frames[0].1)
+    }
+
+    /// Frame shown at the time
+    pub fn frame_at(&self, time: Instant) -> &ImageBrush {
+        let (index, _) = self.position(time);
+        &self.frames[index].0
+    }
+
+    /// Time when the frame after the one shown at `time` must be drawn
+    pub fn next_frame_at(&self, time: Instant) -> Option<Instant> {
+        if self.frames.len() < 2 {
+            return None;
+        }
+        let (_, left) = self.position(time);
+        time.checked_add(left)
+    }
+}
 
 pub struct ImageWidget<M: Clone + Send + 'static> {
     pub image: Option<ImageBrush>,
-    pub width: u32,
-    pub height: u32,
+    pub animation: Option<Arc<Animation>>,
+    pub width: Length,
+    pub height: Length,
+    pub aspect_ratio: Option<f32>,
     pub margin: Margin,
+    pub fit: ContentFit,
+    pub crop: Option<Rect>,
+    pub opacity: f32,
+    pub tint: Option<Color>,
     _marker: PhantomData<M>,
 }
 
@@ -20,9 +78,15 @@ impl<M: Clone + Send + 'static> ImageWidget<M> {
     pub fn new() -> Self {
         Self {
             image: None,
-            width: 0,
-            height: 0,
+            animation: None,
+            width: Length::Shrink,
+            height: Length::Shrink,
+            aspect_ratio: None,
             margin: Margin::new(),
+            fit: ContentFit::Contain,
+            crop: None,
+            opacity: 1.0,
+            tint: None,
             _marker: PhantomData,
         }
     }
@@ -32,78 +96,215 @@ impl<M: Clone + Send + 'static> ImageWidget<M> {
         self
     }
 
-    fn decode_image(
-        &mut self,
-        data: &[u8],
-        width: Option<u32>,
-        height: Option<u32>,
-    ) -> std::io::Result<ImageData> {
-        let mut image = image::ImageReader::new(std::io::Cursor::new(data))
-            .with_guessed_format()?
-            .decode()
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    /// Width of the widget, the image is scaled to it with fit
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
 
-        let target_width = width.unwrap_or(image.width());
-        let target_height = height.unwrap_or(image.height());
+    /// Height of the widget, the image is scaled to it with fit
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
 
-        if image.width() != target_width || image.height() != target_height {
-            image = image.resize_exact(target_width, target_height, FilterType::Triangle);
-        }
+    /// Width divided by height, the size that isn't set is derived from it
+    ///
+    /// By default the ratio of the image is kept
+    pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
 
-        self.width = target_width;
-        self.height = target_height;
+    /// How the image is scaled to fit its size
+    pub fn fit(mut self, fit: ContentFit) -> Self {
+        self.fit = fit;
+        self
+    }
 
-        let data = Arc::new(image.into_rgba8().into_vec());
-        let blob = Blob::new(data);
+    /// Show only a part of the image, in image pixels
+    pub fn crop(mut self, rect: Rect) -> Self {
+        self.crop = Some(rect);
+        self
+    }
 
-        Ok(ImageData {
-            data: blob,
-            format: ImageFormat::Rgba8,
-            width: target_width,
-            height: target_height,
-            alpha_type: peniko::ImageAlphaType::Alpha,
+    /// Opacity from 0.0 (invisible) to 1.0
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Multiply image colors with the color
+    pub fn tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    /// Use decoded image and set widget size, missing size is derived from the image
+    fn set_image(&mut self, image: &DecodedImage, width: Option<u32>, height: Option<u32>) {
+        // Size of widget, the pixels are scaled when drawing
+        self.width = width.map_or(Length::Shrink, |width| Length::Fixed(width as f32));
+        self.height = height.map_or(Length::Shrink, |height| Length::Fixed(height as f32));
+        self.image = Some(image.image.clone());
+        self.animation = image.animation.clone();
+    }
+
+    /// Size of the shown part of the image, only the crop when it is set
+    fn shown_size(&self) -> Option<(f32, f32)> {
+        let image = &self.image.as_ref()?.image;
+        let (width, height) = (image.width as f32, image.height as f32);
+        Some(match self.crop {
+            Some(crop) => crop.size_inside(width, height),
+            None => (width, height),
         })
     }
 
+    /// Image from file, decoded on first use and then taken from cache
     pub fn from_path(
         mut self,
         path: impl AsRef<Path>,
         width: Option<u32>,
         height: Option<u32>,
     ) -> Result<Self, std::io::Error> {
-        let path = path.as_ref();
-        let data = std::fs::read(path)?;
-        let image = Self::decode_image(&mut self, &data, width, height)?;
-        self.image = Some(image.into());
+        let image = ImageHandle::from_path(path).load_blocking()?;
+        self.set_image(&image, width, height);
         Ok(self)
     }
 
+    /// Image from encoded bytes, decoded on first use and then taken from cache
+    ///
+    /// The bytes are hashed to find the image in cache, keep an `ImageHandle` in the app
+    /// and use `handle` instead when the view is built often
     pub fn from_bytes(
         mut self,
-        bytes: &[u8],
+        bytes: impl Into<Arc<[u8]>>,
         width: Option<u32>,
         height: Option<u32>,
     ) -> Result<Self, std::io::Error> {
-        let image = Self::decode_image(&mut self, bytes, width, height)?;
-        self.image = Some(image.into());
+        let image = ImageHandle::from_bytes(bytes).load_blocking()?;
+        self.set_image(&image, width, height);
         Ok(self)
     }
 
+    /// Image that is decoded in background, until it is loaded an empty space is shown
+    ///
+    /// When loading takes a while, set the size so layout doesn't jump.
+    /// An image that can't be decoded stays empty, `ImageHandle::error` tells why
+    /// and `ImageHandle::retry` loads it again
+    pub fn handle(mut self, handle: &ImageHandle, width: Option<u32>, height: Option<u32>) -> Self {
+        match handle.load() {
+            ImageState::Loaded(image) => self.set_image(&image, width, height),
+            _ => {
+                self.width = width.map_or(Length::Shrink, |width| Length::Fixed(width as f32));
+                self.height = height.map_or(Length::Shrink, |height| Length::Fixed(height as f32));
+            }
+        }
+        self
+    }
+
     pub fn build(self) -> Widget<M> {
+        // Keep the ratio of the shown part of the image unless both sizes are given
+        let aspect_ratio = match (self.aspect_ratio, self.shown_size()) {
+            (None, Some((width, height)))
+                if self.width == Length::Shrink || self.height == Length::Shrink =>
+            {
+                Some(width / height.max(1.0))
+            }
+            (aspect_ratio, _) => aspect_ratio,
+        };
+
         let image_style = Style {
             width: self.width,
             height: self.height,
+            aspect_ratio,
             margin: self.margin,
             ..Default::default()
         };
 
+        // Image is still loading or failed to load
+        let Some(image) = self.image else {
+            return Widget {
+                id: next_id(),
+                element: crate::WidgetElement::Spacer {},
+                on_press: None,
+                style: image_style,
+            };
+        };
+
         Widget {
             id: next_id(),
             element: crate::WidgetElement::Image {
-                image: self.image.unwrap(),
+                image,
+                animation: self.animation,
+                fit: self.fit,
+                crop: self.crop,
+                opacity: self.opacity,
+                tint: self.tint.map(|color| (color.r, color.g, color.b, color.a)),
             },
             on_press: None,
             style: image_style,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_handle::load_blocking_in;
+
+    /// Loaded 40x20 image
+    fn image() -> ImageHandle {
+        let handle = ImageHandle::from_rgba(40, 20, vec![255; 40 * 20 * 4]);
+        handle.load_blocking().unwrap();
+        handle
+    }
+
+    #[test]
+    fn ratio_of_image() {
+        let widget = ImageWidget::<()>::new()
+            .handle(&image(), None, None)
+            .build();
+        assert_eq!(widget.style.aspect_ratio, Some(2.0));
+    }
+
+    #[test]
+    fn ratio_of_crop() {
+        let widget = ImageWidget::<()>::new()
+            .handle(&image(), Some(30), None)
+            .crop(Rect::new(0.0, 0.0, 10.0, 20.0))
+            .build();
+        assert_eq!(widget.style.aspect_ratio, Some(0.5));
+    }
+
+    #[test]
+    fn crop_outside_of_image_is_cut() {
+        let widget = ImageWidget::<()>::new()
+            .crop(Rect::new(30.0, 10.0, 20.0, 20.0))
+            .handle(&image(), None, None)
+            .build();
+        assert_eq!(widget.style.aspect_ratio, Some(1.0));
+    }
+
+    #[test]
+    fn no_ratio_with_both_sizes() {
+        let widget = ImageWidget::<()>::new()
+            .handle(&image(), Some(30), Some(30))
+            .crop(Rect::new(0.0, 0.0, 10.0, 20.0))
+            .build();
+        assert_eq!(widget.style.aspect_ratio, None);
+    }
+
+    #[test]
+    fn failed_image_keeps_its_size() {
+        let handle = ImageHandle::from_bytes(vec![1, 2, 3]);
+        let widget = load_blocking_in(|| {
+            ImageWidget::<()>::new()
+                .handle(&handle, Some(30), Some(20))
+                .build()
+        });
+
+        assert!(matches!(widget.element, crate::WidgetElement::Spacer {}));
+        assert_eq!(widget.style.width, Length::Fixed(30.0));
+        assert_eq!(widget.style.height, Length::Fixed(20.0));
+    }
+}
@@ -0,0 +1,487 @@
+use std::{path::PathBuf, sync::Arc};
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    Margin, TextStyle, Widget,
+    color::Color,
+    image_handle::{ImageHandle, ImageState},
+    weight::TextWeight,
+    widget::{
+        hstack::HStack,
+        image_widget::ImageWidget,
+        label::Label,
+        rich_text::{OnLink, RichText, Span},
+        spacer::Spacer,
+        vstack::VStack,
+    },
+};
+
+/// Colors and sizes used to draw markdown
+#[derive(Debug, Clone)]
+pub struct MarkdownTheme {
+    pub text_size: u32,
+    pub text_color: Color,
+    /// Font sizes of headings from level 1 to 6
+    pub heading_sizes: [u32; 6],
+    pub heading_color: Color,
+    pub link_color: Color,
+    pub link_hover_color: Color,
+    pub code_color: Color,
+    pub quote_color: Color,
+    /// Space between blocks
+    pub spacing: i32,
+    /// Indent of lists, block quotes and code blocks
+    pub indent: u32,
+}
+
+impl Default for MarkdownTheme {
+    fn default() -> Self {
+        Self {
+            text_size: 14,
+            text_color: Color::rgb(255, 255, 255),
+            heading_sizes: [32, 26, 22, 18, 16, 14],
+            heading_color: Color::rgb(255, 255, 255),
+            link_color: Color::rgb(54, 104, 237),
+            link_hover_color: Color::rgb(110, 150, 255),
+            code_color: Color::rgb(230, 160, 90),
+            quote_color: Color::rgb(160, 160, 165),
+            spacing: 10,
+            indent: 20,
+        }
+    }
+}
+
+pub struct Markdown<M: Clone + Send + 'static> {
+    pub source: String,
+    pub theme: MarkdownTheme,
+    pub base_path: Option<PathBuf>,
+    pub margin: Margin,
+    pub on_link: Option<OnLink<M>>,
+}
+
+impl<M: Clone + Send + 'static> Markdown<M> {
+    pub fn new(source: String) -> Self {
+        Self {
+            source,
+            theme: MarkdownTheme::default(),
+            base_path: None,
+            margin: Margin::new(),
+            on_link: None,
+        }
+    }
+
+    pub fn theme(mut self, theme: MarkdownTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Directory that relative image paths are resolved from
+    pub fn base_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.base_path = Some(path.into());
+        self
+    }
+
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Message sent when a link is clicked
+    pub fn on_link(mut self, on_link: impl Fn(String) -> M + Send + Sync + 'static) -> Self {
+        self.on_link = Some(Arc::new(on_link));
+        self
+    }
+
+    pub fn build(self) -> Widget<M> {
+        let mut builder = MarkdownBuilder {
+            theme: &self.theme,
+            base_path: self.base_path.as_ref(),
+            on_link: self.on_link.as_ref(),
+            blocks: vec![Block::Root(Vec::new())],
+            spans: Vec::new(),
+            strong: 0,
+            emphasis: 0,
+            strikethrough: 0,
+            quote: 0,
+            heading: None,
+            link: None,
+            image: None,
+            code_block: None,
+        };
+
+        let parser = Parser::new_ext(&self.source, Options::ENABLE_STRIKETHROUGH);
+        for event in parser {
+            builder.event(event);
+        }
+        builder.flush();
+
+        let children = match builder.blocks.pop() {
+            Some(Block::Root(children)) => children,
+            _ => Vec::new(),
+        };
+
+        VStack::new(children)
+            .spacing(self.theme.spacing)
+            .margin(self.margin)
+            .build()
+    }
+}
+
+/// Block that is being built and collects its children
+enum Block<M: Clone + Send + 'static> {
+    Root(Vec<Widget<M>>),
+    Quote(Vec<Widget<M>>),
+    List {
+        next_number: Option<u64>,
+        items: Vec<Widget<M>>,
+    },
+    Item(Vec<Widget<M>>),
+}
+
+impl<M: Clone + Send + 'static> Block<M> {
+    fn children(&mut self) -> &mut Vec<Widget<M>> {
+        match self {
+            Block::Root(children) | Block::Quote(children) | Block::Item(children) => children,
+            Block::List { items, .. } => items,
+        }
+    }
+}
+
+/// Turns markdown events into widgets
+struct MarkdownBuilder<'a, M: Clone + Send + 'static> {
+    theme: &'a MarkdownTheme,
+    base_path: Option<&'a PathBuf>,
+    on_link: Option<&'a OnLink<M>>,
+    blocks: Vec<Block<M>>,
+    spans: Vec<Span>,
+    strong: u32,
+    emphasis: u32,
+    strikethrough: u32,
+    quote: u32,
+    heading: Option<usize>,
+    link: Option<String>,
+    /// Url and alt text of the image being read
+    image: Option<(String, String)>,
+    code_block: Option<String>,
+}
+
+impl<'a, M: Clone + Send + 'static> MarkdownBuilder<'a, M> {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code_block {
+                    code.push_str(&text);
+                } else if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&text);
+                } else {
+                    let span = self.span(&text);
+                    self.spans.push(span);
+                }
+            }
+            Event::Code(code) => {
+                let span = self.span(&code).code().color(self.theme.code_color);
+                self.spans.push(span);
+            }
+            Event::SoftBreak => {
+                let span = self.span(" ");
+                self.spans.push(span);
+            }
+            Event::HardBreak => {
+                let span = self.span("\n");
+                self.spans.push(span);
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                self.heading = Some(level as usize);
+            }
+            Tag::Paragraph => self.flush(),
+            Tag::Strong => self.strong += 1,
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link { dest_url, .. } => self.link = Some(dest_url.to_string()),
+            Tag::Image { dest_url, .. } => self.image = Some((dest_url.to_string(), String::new())),
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.code_block = Some(String::new());
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote += 1;
+                self.blocks.push(Block::Quote(Vec::new()));
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.blocks.push(Block::List {
+                    next_number: start,
+                    items: Vec::new(),
+                });
+            }
+            Tag::Item => {
+                self.flush();
+                self.blocks.push(Block::Item(Vec::new()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.heading = None;
+            }
+            TagEnd::Paragraph => self.flush(),
+            TagEnd::Strong => self.strong -= 1,
+            TagEnd::Emphasis => self.emphasis -= 1,
+            TagEnd::Strikethrough => self.strikethrough -= 1,
+            TagEnd::Link => self.link = None,
+            TagEnd::Image => {
+                if let Some((url, alt)) = self.image.take() {
+                    self.flush();
+                    let image = self.image_widget(&url, &alt);
+                    self.push(image);
+                }
+            }
+            TagEnd::CodeBlock => {
+                if let Some(code) = self.code_block.take() {
+                    let text = RichText::new(vec![Span::new(code.trim_end().to_string()).code()])
+                        .size(self.theme.text_size)
+                        .color(self.theme.code_color)
+                        .build();
+                    let indented = self.indented(text);
+                    self.push(indented);
+                }
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote -= 1;
+                if let Some(Block::Quote(children)) = self.blocks.pop() {
+                    let quote = VStack::new(children).spacing(self.theme.spacing).build();
+                    let indented = self.indented(quote);
+                    self.push(indented);
+                }
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                if let Some(Block::List { items, .. }) = self.blocks.pop() {
+                    let list = VStack::new(items).spacing(self.theme.spacing / 2).build();
+                    let indented = self.indented(list);
+                    self.push(indented);
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                if let Some(Block::Item(children)) = self.blocks.pop() {
+                    let marker = match self.blocks.last_mut() {
+                        Some(Block::List {
+                            next_number: Some(number),
+                            ..
+                        }) => {
+                            *number += 1;
+                            format!("{}.", *number - 1)
+                        }
+                        _ => "•".to_string(),
+                    };
+                    let marker = Label::new(marker)
+                        .size(self.theme.text_size)
+                        .color(self.text_color())
+                        .build();
+                    let content = VStack::new(children).spacing(self.theme.spacing).build();
+                    let item = HStack::new(vec![marker, content]).spacing(8).build();
+                    self.push(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text_color(&self) -> Color {
+        if self.quote > 0 {
+            self.theme.quote_color
+        } else {
+            self.theme.text_color
+        }
+    }
+
+    /// Create a span with the current inline style
+    fn span(&self, content: &str) -> Span {
+        let mut span = Span::new(content.to_string()).color(self.text_color());
+
+        if let Some(level) = self.heading {
+            span = span
+                .size(self.theme.heading_sizes[level.clamp(1, 6) - 1])
+                .color(self.theme.heading_color)
+                .weight(TextWeight::BOLD);
+        } else if self.strong > 0 {
+            span = span.weight(TextWeight::BOLD);
+        }
+
+        match (self.emphasis > 0, self.strikethrough > 0) {
+            (true, true) => span = span.style(TextStyle::ItalicStriketrough),
+            (false, true) => span = span.style(TextStyle::Striketrough),
+            (true, false) => span = span.style(TextStyle::Italic),
+            (false, false) => {}
+        }
+
+        if let Some(url) = &self.link {
+            span = span.link(url);
+            span.color = None;
+        }
+
+        span
+    }
+
+    /// Turn collected spans into a rich text
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+
+        let spans = std::mem::take(&mut self.spans);
+        let mut text = RichText::new(spans)
+            .size(self.theme.text_size)
+            .color(self.text_color())
+            .link_color(self.theme.link_color)
+            .link_hover_color(self.theme.link_hover_color);
+        text.on_link = self.on_link.cloned();
+
+        let text = text.build();
+        self.push(text);
+    }
+
+    fn image_widget(&self, url: &str, alt: &str) -> Widget<M> {
+        let path = match self.base_path {
+            Some(base_path) => base_path.join(url),
+            None => PathBuf::from(url),
+        };
+
+        // Decoded in background, the view is built again once it is loaded
+        let handle = ImageHandle::from_path(path);
+        match handle.load() {
+            // Show alt text when the image can't be loaded
+            ImageState::Failed(_) => Label::new(alt.to_string())
+                .size(self.theme.text_size)
+                .color(self.theme.quote_color)
+                .style(TextStyle::Italic)
+                .build(),
+            _ => ImageWidget::new().handle(&handle, None, None).build(),
+        }
+    }
+
+    fn indented(&self, child: Widget<M>) -> Widget<M> {
+        let indent = Spacer::new().width(self.theme.indent).build();
+        HStack::new(vec![indent, child]).build()
+    }
+
+    fn push(&mut self, widget: Widget<M>) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.children().push(widget);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{WidgetElement, widget::rich_text::TextSpan};
+
+    /// Blocks of the rendered markdown
+    fn blocks(source: &str) -> Vec<Widget<()>> {
+        match Markdown::new(source.to_string()).build().element {
+            WidgetElement::VStack { children } => children,
+            element => panic!("expected a vstack, got {element:?}"),
+        }
+    }
+
+    fn spans(widget: &Widget<()>) -> &[TextSpan] {
+        match &widget.element {
+            WidgetElement::RichText { spans, .. } => spans,
+            element => panic!("expected a rich text, got {element:?}"),
+        }
+    }
+
+    fn children(widget: &Widget<()>) -> &[Widget<()>] {
+        match &widget.element {
+            WidgetElement::VStack { children } | WidgetElement::HStack { children } => children,
+            element => panic!("expected a stack, got {element:?}"),
+        }
+    }
+
+    #[test]
+    fn headings_use_heading_sizes() {
+        let blocks = blocks("# Title\n\n### Section\n\ntext");
+        let theme = MarkdownTheme::default();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(spans(&blocks[0])[0].font_size, theme.heading_sizes[0]);
+        assert_eq!(spans(&blocks[0])[0].weight, TextWeight::BOLD);
+        assert_eq!(spans(&blocks[1])[0].font_size, theme.heading_sizes[2]);
+        assert_eq!(spans(&blocks[2])[0].font_size, theme.text_size);
+    }
+
+    #[test]
+    fn lists_have_markers() {
+        let blocks = blocks("- one\n- two\n\n3. three\n4. four");
+        assert_eq!(blocks.len(), 2);
+
+        let markers = |list: &Widget<()>| -> Vec<String> {
+            // Indent spacer, then the items
+            children(&children(list)[1])
+                .iter()
+                .map(|item| match &children(item)[0].element {
+                    WidgetElement::Label { content, .. } => content.clone(),
+                    element => panic!("expected a marker label, got {element:?}"),
+                })
+                .collect()
+        };
+        assert_eq!(markers(&blocks[0]), ["•", "•"]);
+        assert_eq!(markers(&blocks[1]), ["3.", "4."]);
+    }
+
+    #[test]
+    fn code_is_monospace() {
+        let blocks = blocks("Run `cargo test`\n\n```\nfn main() {}\n```");
+        assert_eq!(blocks.len(), 2);
+
+        let inline = spans(&blocks[0]);
+        assert!(!inline[0].code);
+        assert!(inline[1].code);
+        assert_eq!(inline[1].content, "cargo test");
+
+        // Code block is indented
+        let code = spans(&children(&blocks[1])[1]);
+        assert!(code[0].code);
+        assert_eq!(code[0].content, "fn main() {}");
+    }
+
+    #[test]
+    fn links_keep_their_url() {
+        let blocks = blocks("See [the docs](https://example.com) here");
+        let spans = spans(&blocks[0]);
+
+        assert_eq!(spans[1].content, "the docs");
+        assert_eq!(spans[1].link.as_deref(), Some("https://example.com"));
+        assert_eq!(spans[1].style, TextStyle::Underline);
+        assert_eq!(spans[0].link, None);
+    }
+
+    #[test]
+    fn nested_emphasis_is_combined() {
+        let blocks = blocks("***both*** ~~*struck*~~ *~~struck~~*");
+        let spans = spans(&blocks[0]);
+
+        assert_eq!(spans[0].weight, TextWeight::BOLD);
+        assert_eq!(spans[0].style, TextStyle::Italic);
+        assert_eq!(spans[2].style, TextStyle::ItalicStriketrough);
+        assert_eq!(spans[4].style, TextStyle::ItalicStriketrough);
+    }
+}
@@ -3,5 +3,8 @@ pub mod container;
 pub mod hstack;
 pub mod image_widget;
 pub mod label;
+pub mod markdown;
+pub mod rich_text;
 pub mod spacer;
 pub mod vstack;
+pub mod wrap;
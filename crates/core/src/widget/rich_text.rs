@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use crate::{
+    Margin, TextStyle, Widget, color::Color, id::next_id, style::Style, weight::TextWeight,
+};
+
+/// Callback that turns the url of a clicked link into a message
+pub type OnLink<M> = Arc<dyn Fn(String) -> M + Send + Sync>;
+
+/// Piece of text inside a rich text, every unset property is taken from the rich text
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub content: String,
+    pub size: Option<u32>,
+    pub weight: Option<TextWeight>,
+    pub style: Option<TextStyle>,
+    pub color: Option<Color>,
+    pub link: Option<String>,
+    pub code: bool,
+}
+
+impl Span {
+    pub fn new(content: String) -> Self {
+        Self {
+            content,
+            size: None,
+            weight: None,
+            style: None,
+            color: None,
+            link: None,
+            code: false,
+        }
+    }
+
+    pub fn size(mut self, font_size: u32) -> Self {
+        self.size = Some(font_size);
+        self
+    }
+
+    pub fn weight(mut self, weight: TextWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn style(mut self, style: TextStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw the span with a monospace font
+    pub fn code(mut self) -> Self {
+        self.code = true;
+        self
+    }
+
+    /// Make the span a link, the url is given to `on_link` when the span is clicked
+    pub fn link(mut self, url: &str) -> Self {
+        self.link = Some(url.to_string());
+        self
+    }
+}
+
+/// Span with all properties resolved, used by the layout engine and renderer
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TextSpan {
+    pub content: String,
+    pub font_size: u32,
+    pub weight: TextWeight,
+    pub style: TextStyle,
+    pub color: (u8, u8, u8, u8),
+    pub link: Option<String>,
+    pub code: bool,
+}
+
+pub struct RichText<M: Clone + Send + 'static> {
+    pub spans: Vec<Span>,
+    pub font_size: u32,
+    pub weight: TextWeight,
+    pub style: TextStyle,
+    pub spacing: i32,
+    pub color: Color,
+    pub link_color: Color,
+    pub link_hover_color: Color,
+    pub margin: Margin,
+    pub on_press: Option<M>,
+    pub on_link: Option<OnLink<M>>,
+}
+
+impl<M: Clone + Send + 'static> RichText<M> {
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self {
+            spans,
+            font_size: 14,
+            weight: TextWeight::NORMAL,
+            style: TextStyle::Normal,
+            spacing: 0,
+            color: Color::rgb(255, 255, 255),
+            link_color: Color::rgb(54, 104, 237),
+            link_hover_color: Color::rgb(110, 150, 255),
+            margin: Margin::new(),
+            on_press: None,
+            on_link: None,
+        }
+    }
+
+    pub fn push(&mut self, span: Span) {
+        self.spans.push(span);
+    }
+
+    /// Default font size of spans
+    pub fn size(mut self, font_size: u32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Default weight of spans
+    pub fn weight(mut self, weight: TextWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Default style of spans
+    pub fn style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Extra spacing between letters
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Default color of spans
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Color of links
+    pub fn link_color(mut self, color: Color) -> Self {
+        self.link_color = color;
+        self
+    }
+
+    /// Color of the link under the cursor
+    pub fn link_hover_color(mut self, color: Color) -> Self {
+        self.link_hover_color = color;
+        self
+    }
+
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn on_press(mut self, m: M) -> Self {
+        self.on_press = Some(m);
+        self
+    }
+
+    /// Message sent when a link is clicked
+    pub fn on_link(mut self, on_link: impl Fn(String) -> M + Send + Sync + 'static) -> Self {
+        self.on_link = Some(Arc::new(on_link));
+        self
+    }
+
+    pub fn build(self) -> Widget<M> {
+        let spans = self
+            .spans
+            .into_iter()
+            .map(|span| {
+                // Links are underlined and use link color, unless the span says otherwise
+                let (default_color, default_style) = if span.link.is_some() {
+                    (self.link_color, TextStyle::Underline)
+                } else {
+                    (self.color, self.style.clone())
+                };
+                let color = span.color.unwrap_or(default_color);
+
+                TextSpan {
+                    content: span.content,
+                    font_size: span.size.unwrap_or(self.font_size),
+                    weight: span.weight.unwrap_or(self.weight.clone()),
+                    style: span.style.unwrap_or(default_style),
+                    color: (color.r, color.g, color.b, color.a),
+                    link: span.link,
+                    code: span.code,
+                }
+            })
+            .collect();
+
+        let hover = self.link_hover_color;
+
+        // Text style
+        let text_style = Style {
+            margin: self.margin,
+            spacing: self.spacing,
+            ..Default::default()
+        };
+
+        Widget {
+            id: next_id(),
+            element: crate::WidgetElement::RichText {
+                spans,
+                link_hover_color: (hover.r, hover.g, hover.b, hover.a),
+                on_link: self.on_link,
+            },
+            on_press: self.on_press,
+            style: text_style,
+        }
+    }
+}
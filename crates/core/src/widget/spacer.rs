@@ -1,29 +1,29 @@
 use std::marker::PhantomData;
 
-use crate::{Widget, id::next_id, style::Style};
+use crate::{Length, Widget, id::next_id, style::Style};
 
 pub struct Spacer<M: Clone + Send + 'static> {
-    pub width: u32,
-    pub height: u32,
+    pub width: Length,
+    pub height: Length,
     _marker: PhantomData<M>,
 }
 
 impl<M: Clone + Send + 'static> Spacer<M> {
     pub fn new() -> Self {
         Self {
-            width: 0,
-            height: 0,
+            width: Length::Fixed(0.0),
+            height: Length::Fixed(0.0),
             _marker: PhantomData,
         }
     }
 
-    pub fn width(mut self, width: u32) -> Self {
-        self.width = width;
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
         self
     }
 
-    pub fn height(mut self, height: u32) -> Self {
-        self.height = height;
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
         self
     }
 
@@ -0,0 +1,97 @@
+use crate::{CrossAlign, Length, Margin, Widget, id::next_id, style::Style};
+
+/// Places children in rows, a child that doesn't fit in the row starts a new one
+#[derive(Debug)]
+pub struct Wrap<M: Clone + Send + 'static> {
+    pub children: Vec<Widget<M>>,
+    pub horizontal_spacing: i32,
+    pub vertical_spacing: i32,
+    pub cross_align: CrossAlign,
+    pub width: Length,
+    pub height: Length,
+    pub margin: Margin,
+}
+
+impl<M: Clone + Send + 'static> Wrap<M> {
+    pub fn new(children: Vec<Widget<M>>) -> Self {
+        Self {
+            children,
+            horizontal_spacing: 0,
+            vertical_spacing: 0,
+            cross_align: CrossAlign::Start,
+            width: Length::Shrink,
+            height: Length::Shrink,
+            margin: Margin::new(),
+        }
+    }
+
+    pub fn extend(&mut self, children: Vec<Widget<M>>) {
+        self.children.extend(children);
+    }
+
+    pub fn push(&mut self, child: Widget<M>) {
+        self.children.push(child);
+    }
+
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Space between children and between rows
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.horizontal_spacing = spacing;
+        self.vertical_spacing = spacing;
+        self
+    }
+
+    /// Space between children in a row
+    pub fn horizontal_spacing(mut self, spacing: i32) -> Self {
+        self.horizontal_spacing = spacing;
+        self
+    }
+
+    /// Space between rows
+    pub fn vertical_spacing(mut self, spacing: i32) -> Self {
+        self.vertical_spacing = spacing;
+        self
+    }
+
+    /// How children are aligned vertically inside their row
+    pub fn cross_align(mut self, align: CrossAlign) -> Self {
+        self.cross_align = align;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    pub fn build(self) -> Widget<M> {
+        // Wrap style
+        let wrap_style = Style {
+            spacing: self.horizontal_spacing,
+            cross_align: self.cross_align,
+            margin: self.margin,
+            width: self.width,
+            height: self.height,
+            ..Default::default()
+        };
+
+        Widget {
+            id: next_id(),
+            element: crate::WidgetElement::Wrap {
+                children: self.children,
+                vertical_spacing: self.vertical_spacing,
+            },
+            on_press: None,
+            style: wrap_style,
+        }
+    }
+}
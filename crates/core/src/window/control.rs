@@ -1,5 +1,5 @@
 use crate::{
-    color::Color,
+    background::Background,
     window::{attention::UserAttention, theme::Theme},
 };
 use std::sync::Arc;
@@ -9,7 +9,7 @@ use winit::{
 /// Public API for changing window settings in runtime
 pub struct Window<'window> {
     pub window: Arc<WinitWindow>,
-    pub background: &'window mut Color,
+    pub background: &'window mut Background,
     pub eventloop: &'window ActiveEventLoop,
 }
 
@@ -19,14 +19,21 @@ impl<'window> Window<'window> {
         self.window.set_title(title);
     }
 
-    /// Window width
+    /// Window width in logical pixels, like the layout
     pub fn width(&mut self) -> u32 {
-        self.window.inner_size().width
+        let size = self.window.inner_size();
+        size.to_logical::<f64>(self.window.scale_factor()).width as u32
     }
 
-    /// Window height
+    /// Window height in logical pixels, like the layout
     pub fn height(&mut self) -> u32 {
-        self.window.inner_size().height
+        let size = self.window.inner_size();
+        size.to_logical::<f64>(self.window.scale_factor()).height as u32
+    }
+
+    /// Physical pixels per logical pixel of the display the window is on
+    pub fn scale_factor(&mut self) -> f64 {
+        self.window.scale_factor()
     }
 
     pub fn is_decorated(&mut self) -> bool {
@@ -45,12 +52,9 @@ impl<'window> Window<'window> {
         self.window.request_redraw();
     }
 
-    /// Window background
-    pub fn background(&mut self, color: Color) {
-        self.background.r = color.r;
-        self.background.g = color.g;
-        self.background.b = color.b;
-        self.background.a = color.a;
+    /// Window background, a solid color or a gradient
+    pub fn background(&mut self, background: impl Into<Background>) {
+        *self.background = background.into();
     }
 
     pub fn request_user_attention(&mut self, attention: UserAttention) {
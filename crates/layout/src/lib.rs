@@ -1,9 +1,19 @@
-use std::{collections::HashMap, marker::PhantomData};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+};
 
-use glazeui_core::{Align, Widget, WidgetElement, style::Style};
-use parley::{FontContext, LayoutContext};
+use glazeui_core::{
+    Absolute, Align, CrossAlign, Direction, Justify, Length, Position, Widget, WidgetElement,
+    style::Style,
+};
+use parley::{Alignment, AlignmentOptions, FontContext, Layout, LayoutContext};
 
-use crate::measure::text::measure_text;
+use crate::measure::{
+    rich_text::{LinkArea, link_areas, rich_text_layout},
+    text::{TextBrush, text_layout},
+};
 pub mod measure;
 
 #[derive(Clone, Debug)]
@@ -16,8 +26,78 @@ pub struct LayoutNode {
     pub parent_height: f32,
 }
 
+/// Main axis of a stack
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    /// Turn width and height into main and cross size
+    fn main_cross(self, width: f32, height: f32) -> (f32, f32) {
+        match self {
+            Axis::Horizontal => (width, height),
+            Axis::Vertical => (height, width),
+        }
+    }
+
+    /// Turn main and cross size into width and height
+    fn width_height(self, main: f32, cross: f32) -> (f32, f32) {
+        match self {
+            Axis::Horizontal => (main, cross),
+            Axis::Vertical => (cross, main),
+        }
+    }
+
+    fn main_length(self, style: &Style) -> &Length {
+        match self {
+            Axis::Horizontal => &style.width,
+            Axis::Vertical => &style.height,
+        }
+    }
+
+    fn main_length_mut(self, style: &mut Style) -> &mut Length {
+        match self {
+            Axis::Horizontal => &mut style.width,
+            Axis::Vertical => &mut style.height,
+        }
+    }
+
+    fn cross_length_mut(self, style: &mut Style) -> &mut Length {
+        match self {
+            Axis::Horizontal => &mut style.height,
+            Axis::Vertical => &mut style.width,
+        }
+    }
+}
+
+/// Shaped text of a label or rich text, reused while its content doesn't change
+struct CachedText {
+    /// Hash of everything the text was shaped from
+    key: u64,
+    layout: Layout<TextBrush>,
+    /// Width the lines were broken at
+    max_width: Option<f32>,
+}
+
+/// Keeps the layout of the last frame, create it once and call `compute` every frame
 pub struct LayoutEngine<M: Clone + Send + 'static> {
     nodes: HashMap<u64, LayoutNode>,
+    links: HashMap<u64, Vec<LinkArea>>,
+    /// Ids of rich texts in the order they are painted, the last one is on top
+    link_order: Vec<u64>,
+    /// Baseline of the first text line, from the top of the node
+    baselines: HashMap<u64, f32>,
+    /// Shaped text by widget id, kept between frames
+    texts: HashMap<u64, CachedText>,
+    registred_fallback_font: bool,
+    /// Device pixels per layout pixel, text is shaped at this scale
+    scale: f32,
+    /// Direction of the app, used when no widget sets one
+    base_direction: Direction,
+    /// Direction of the subtree being laid out
+    direction: Direction,
     _marker: PhantomData<M>,
 }
 
@@ -26,26 +106,393 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
+            links: HashMap::new(),
+            link_order: Vec::new(),
+            baselines: HashMap::new(),
+            texts: HashMap::new(),
+            registred_fallback_font: false,
+            scale: 1.0,
+            base_direction: Direction::Ltr,
+            direction: Direction::Ltr,
             _marker: PhantomData,
         }
     }
 
+    /// Direction of the whole app, right to left mirrors rows, alignment and text
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.base_direction = direction;
+        self
+    }
+
     /// Get information of widget (pos, size, parent size) by id
     pub fn get(&self, id: u64) -> Option<&LayoutNode> {
         self.nodes.get(&id)
     }
 
-    /// Compute layout
+    /// Get the link (widget id, span index) at the position
+    ///
+    /// When links overlap, the one painted last (on top) is found
+    pub fn link_at(&self, x: f32, y: f32) -> Option<(u64, usize)> {
+        self.link_order.iter().rev().find_map(|id| {
+            self.links
+                .get(id)?
+                .iter()
+                .find(|area| {
+                    x >= area.x
+                        && x <= area.x + area.width
+                        && y >= area.y
+                        && y <= area.y + area.height
+                })
+                .map(|area| (*id, area.span))
+        })
+    }
+
+    /// Device pixels per layout pixel of the last `compute`
+    ///
+    /// Shaped text is in device pixels, layout nodes are in layout (logical) pixels
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Direction of the whole app, widgets without their own direction take it from their parent
+    pub fn base_direction(&self) -> Direction {
+        self.base_direction
+    }
+
+    /// Get the shaped text of a label or rich text, lines are broken at the widget width
+    pub fn text_layout(&self, id: u64) -> Option<&Layout<TextBrush>> {
+        self.texts.get(&id).map(|text| &text.layout)
+    }
+
+    /// Compute layout, text that didn't change since the last frame isn't shaped again
+    ///
+    /// `width` and `height` are in logical pixels, `scale` is the scale factor of the display
     pub fn compute(
         &mut self,
         root: &Widget<M>,
         width: f32,
         height: f32,
+        scale: f32,
+        registred_fallback_font: bool,
         font_cx: &mut FontContext,
-        layout_cx: &mut LayoutContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
     ) {
+        self.nodes.clear();
+        self.links.clear();
+        self.link_order.clear();
+        self.baselines.clear();
+
+        // Text is shaped with a different font when the fallback font changes
+        if self.registred_fallback_font != registred_fallback_font {
+            self.texts.clear();
+            self.registred_fallback_font = registred_fallback_font;
+        }
+
+        // Text is shaped again for the new pixel density
+        if self.scale != scale {
+            self.texts.clear();
+            self.scale = scale;
+        }
+
+        self.direction = self.base_direction;
+
         // Start at (0, 0) with available window size
         self.resolve_node(root, 0.0, 0.0, width, height, font_cx, layout_cx);
+        self.align_child(root, width, height);
+        Self::paint_order(root, &mut self.link_order);
+
+        // Forget text of widgets that are gone
+        let nodes = &self.nodes;
+        self.texts.retain(|id, _| nodes.contains_key(id));
+    }
+
+    /// Collect ids of rich texts in the order they are drawn, parents before children
+    fn paint_order(widget: &Widget<M>, order: &mut Vec<u64>) {
+        match &widget.element {
+            WidgetElement::RichText { .. } => order.push(widget.id),
+            WidgetElement::Container { child, .. } => Self::paint_order(child, order),
+            WidgetElement::VStack { children }
+            | WidgetElement::HStack { children }
+            | WidgetElement::Wrap { children, .. } => {
+                for child in children {
+                    Self::paint_order(child, order);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the shaped text of the widget, shaping it only when the key changed
+    fn cached_text(
+        &mut self,
+        id: u64,
+        key: u64,
+        max_width: Option<f32>,
+        shape: impl FnOnce() -> Layout<TextBrush>,
+    ) -> &Layout<TextBrush> {
+        let changed = self.texts.get(&id).is_none_or(|text| text.key != key);
+        if changed {
+            let layout = shape();
+            self.texts.insert(
+                id,
+                CachedText {
+                    key,
+                    layout,
+                    max_width: None,
+                },
+            );
+        }
+
+        let text = self.texts.get_mut(&id).unwrap();
+        // Breaking lines is cheap compared to shaping, do it only when the width changed
+        if changed || text.max_width != max_width {
+            text.layout.break_all_lines(max_width);
+            text.max_width = max_width;
+        }
+        &text.layout
+    }
+
+    /// Align the lines of the text to the right edge of the widget, for right to left text
+    ///
+    /// Parley finds the direction of each paragraph from its text, so text that starts
+    /// with latin characters is still shaped left to right, only its lines are moved
+    fn align_text_right(&mut self, id: u64, width: f32) {
+        if let Some(text) = self.texts.get_mut(&id) {
+            text.layout
+                .align(Some(width), Alignment::Right, AlignmentOptions::default());
+        }
+    }
+
+    fn is_rtl(&self) -> bool {
+        self.direction == Direction::Rtl
+    }
+
+    /// Inset from the left edge of a box, right to left uses the right side instead
+    fn leading(&self, left: i32, right: i32) -> f32 {
+        match self.direction {
+            Direction::Ltr => left as f32,
+            Direction::Rtl => right as f32,
+        }
+    }
+
+    /// Baseline of the first line of the text
+    fn first_baseline(layout: &Layout<TextBrush>) -> f32 {
+        layout
+            .lines()
+            .next()
+            .map_or(0.0, |line| line.metrics().baseline)
+    }
+
+    /// Size for the length in the available space, `content` is the size of shrinked widget
+    fn resolve_length(length: &Length, available: f32, content: f32) -> f32 {
+        match length {
+            Length::Fixed(size) => *size,
+            Length::Shrink => content,
+            Length::Fill | Length::FillPortion(_) => available,
+            Length::Percent(percent) => available * percent / 100.0,
+        }
+    }
+
+    /// Width of the widget in the available space, kept between its min and max width
+    fn resolve_width(style: &Style, available: f32, content: f32) -> f32 {
+        let width = Self::resolve_length(&style.width, available, content);
+        Self::clamp_size(width, style.min_width, style.max_width)
+    }
+
+    /// Height of the widget in the available space, kept between its min and max height
+    fn resolve_height(style: &Style, available: f32, content: f32) -> f32 {
+        let height = Self::resolve_length(&style.height, available, content);
+        Self::clamp_size(height, style.min_height, style.max_height)
+    }
+
+    fn clamp_size(size: f32, min: Option<u32>, max: Option<u32>) -> f32 {
+        let size = max.map_or(size, |max| size.min(max as f32));
+        min.map_or(size, |min| size.max(min as f32))
+    }
+
+    /// Derive the size that isn't set by the style from the aspect ratio (width / height)
+    ///
+    /// When both sizes are set the ratio is ignored, when none is the height is derived
+    fn apply_aspect_ratio(style: &Style, width: f32, height: f32) -> (f32, f32) {
+        let Some(ratio) = style.aspect_ratio.filter(|ratio| *ratio > 0.0) else {
+            return (width, height);
+        };
+
+        // The derived size is kept between its min and max. A set size is never changed,
+        // when it can't keep the ratio the ratio gives way
+        if style.height == Length::Shrink {
+            let derived = width / ratio;
+            let height = Self::clamp_size(derived, style.min_height, style.max_height);
+            if height == derived || style.width != Length::Shrink {
+                return (width, height);
+            }
+            // Neither size is set, the width follows the clamped height
+            let width = Self::clamp_size(height * ratio, style.min_width, style.max_width);
+            (width, height)
+        } else if style.width == Length::Shrink {
+            let width = Self::clamp_size(height * ratio, style.min_width, style.max_width);
+            (width, height)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Size of the laid out widget with its margin
+    fn outer_size(&self, widget: &Widget<M>) -> (f32, f32) {
+        let margin = &widget.style.margin;
+        match self.nodes.get(&widget.id) {
+            Some(node) => (
+                node.width + (margin.left + margin.right) as f32,
+                node.height + (margin.top + margin.bottom) as f32,
+            ),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Move the laid out widget and its children
+    fn translate(&mut self, widget: &Widget<M>, x: f32, y: f32) {
+        if x == 0.0 && y == 0.0 {
+            return;
+        }
+
+        if let Some(node) = self.nodes.get_mut(&widget.id) {
+            node.x += x;
+            node.y += y;
+        }
+        if let Some(links) = self.links.get_mut(&widget.id) {
+            for area in links {
+                area.x += x;
+                area.y += y;
+            }
+        }
+
+        match &widget.element {
+            WidgetElement::VStack { children }
+            | WidgetElement::HStack { children }
+            | WidgetElement::Wrap { children, .. } => {
+                for child in children {
+                    self.translate(child, x, y);
+                }
+            }
+            WidgetElement::Container { child, .. } => self.translate(child, x, y),
+            _ => {}
+        }
+    }
+
+    /// Move the widget laid out at the start of the area by its align
+    fn align_child(&mut self, widget: &Widget<M>, available_width: f32, available_height: f32) {
+        let (width, height) = self.outer_size(widget);
+        let (mut x_offset, y_offset) = self.get_align_offset(
+            available_height,
+            available_width,
+            width,
+            height,
+            &widget.style.align,
+        );
+        // Left and right are swapped right to left, unaligned widget starts at the right
+        if self.is_rtl() {
+            x_offset = available_width - width - x_offset;
+        }
+        self.translate(widget, x_offset, y_offset);
+    }
+
+    fn is_absolute(widget: &Widget<M>) -> bool {
+        matches!(widget.style.position, Position::Absolute(_))
+    }
+
+    /// Layout an absolute child relative to the box of its parent
+    fn place_absolute(
+        &mut self,
+        widget: &Widget<M>,
+        parent_x: f32,
+        parent_y: f32,
+        parent_width: f32,
+        parent_height: f32,
+        font_cx: &mut FontContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
+    ) {
+        let Position::Absolute(Absolute {
+            left,
+            top,
+            right,
+            bottom,
+        }) = widget.style.position
+        else {
+            return;
+        };
+        // Right to left mirrors the edges, `left` is taken from the start edge on the right
+        let (left, right) = if self.is_rtl() {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        // Space between the set edges
+        let available_width = (parent_width - left.unwrap_or(0.0) - right.unwrap_or(0.0)).max(0.0);
+        let available_height =
+            (parent_height - top.unwrap_or(0.0) - bottom.unwrap_or(0.0)).max(0.0);
+
+        // Shrinked widget with both edges set is stretched between them
+        let mut style = widget.style.clone();
+        if left.is_some() && right.is_some() && style.width == Length::Shrink {
+            style.width = Length::Fill;
+        }
+        if top.is_some() && bottom.is_some() && style.height == Length::Shrink {
+            style.height = Length::Fill;
+        }
+
+        self.layout_node(
+            widget,
+            &style,
+            parent_x,
+            parent_y,
+            available_width,
+            available_height,
+            font_cx,
+            layout_cx,
+        );
+
+        let (width, height) = self.outer_size(widget);
+        let x = match (left, right) {
+            (Some(left), _) => left,
+            (None, Some(right)) => parent_width - right - width,
+            (None, None) => 0.0,
+        };
+        let y = match (top, bottom) {
+            (Some(top), _) => top,
+            (None, Some(bottom)) => parent_height - bottom - height,
+            (None, None) => 0.0,
+        };
+        self.translate(widget, x, y);
+    }
+
+    /// Distance from the top of the laid out widget to its first baseline
+    ///
+    /// Widgets without text use their bottom edge
+    fn baseline(&self, widget: &Widget<M>) -> f32 {
+        let Some(node) = self.nodes.get(&widget.id) else {
+            return 0.0;
+        };
+
+        let first_child = match &widget.element {
+            WidgetElement::Container { child, .. } => Some(child.as_ref()),
+            WidgetElement::VStack { children }
+            | WidgetElement::HStack { children }
+            | WidgetElement::Wrap { children, .. } => children.first(),
+            _ => None,
+        };
+
+        match first_child {
+            Some(child) => match self.nodes.get(&child.id) {
+                Some(child_node) => self.baseline(child) + child_node.y - node.y,
+                None => node.height,
+            },
+            None => self
+                .baselines
+                .get(&widget.id)
+                .copied()
+                .unwrap_or(node.height),
+        }
     }
 
     /// Resolve layout for a node and its children
@@ -57,40 +504,94 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
         available_width: f32,
         available_height: f32,
         font_cx: &mut FontContext,
-        layout_cx: &mut LayoutContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
     ) {
+        self.layout_node(
+            widget,
+            &widget.style,
+            parent_x,
+            parent_y,
+            available_width,
+            available_height,
+            font_cx,
+            layout_cx,
+        );
+    }
+
+    /// Resolve layout for a node, `layout_style` is used instead of the widget style
+    fn layout_node(
+        &mut self,
+        widget: &Widget<M>,
+        layout_style: &Style,
+        parent_x: f32,
+        parent_y: f32,
+        available_width: f32,
+        available_height: f32,
+        font_cx: &mut FontContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
+    ) {
+        // Margin moves the node and takes space from the available size
+        let margin = &layout_style.margin;
+        let parent_x = parent_x + self.leading(margin.left, margin.right);
+        let parent_y = parent_y + margin.top as f32;
+        let available_width = (available_width - (margin.left + margin.right) as f32).max(0.0);
+        let available_height = (available_height - (margin.top + margin.bottom) as f32).max(0.0);
+
+        // Direction is inherited by the children
+        let parent_direction = self.direction;
+        if let Some(direction) = layout_style.direction {
+            self.direction = direction;
+        }
+
         match &widget.element {
             WidgetElement::VStack { children } => {
-                self.layout_vstack(
+                self.layout_stack(
                     widget.id,
-                    &widget.style,
+                    layout_style,
                     children,
+                    Axis::Vertical,
                     parent_x,
                     parent_y,
-                    available_height,
                     available_width,
-                    widget.style.spacing as f32,
+                    available_height,
                     font_cx,
                     layout_cx,
                 );
             }
             WidgetElement::HStack { children } => {
-                self.layout_hstack(
+                self.layout_stack(
                     widget.id,
-                    &widget.style,
+                    layout_style,
                     children,
+                    Axis::Horizontal,
                     parent_x,
                     parent_y,
+                    available_width,
                     available_height,
+                    font_cx,
+                    layout_cx,
+                );
+            }
+            WidgetElement::Wrap {
+                children,
+                vertical_spacing,
+            } => {
+                self.layout_wrap(
+                    widget.id,
+                    layout_style,
+                    children,
+                    *vertical_spacing as f32,
+                    parent_x,
+                    parent_y,
                     available_width,
-                    widget.style.spacing as f32,
+                    available_height,
                     font_cx,
                     layout_cx,
                 );
             }
             WidgetElement::Spacer { .. } => {
-                let width = widget.style.width as f32;
-                let height = widget.style.height as f32;
+                let width = Self::resolve_width(layout_style, available_width, 0.0);
+                let height = Self::resolve_height(layout_style, available_height, 0.0);
 
                 let spacer_node = LayoutNode {
                     x: parent_x,
@@ -103,8 +604,74 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
                 self.nodes.insert(widget.id, spacer_node);
             }
             WidgetElement::Container { child, .. } => {
-                let container_width = widget.style.width as f32;
-                let container_height = widget.style.height as f32;
+                let padding = &layout_style.padding;
+                let padding_width = (padding.left + padding.right) as f32;
+                let padding_height = (padding.top + padding.bottom) as f32;
+
+                // Shrinked container gives the child all available space
+                let width = Self::resolve_width(layout_style, available_width, available_width);
+                let height = Self::resolve_height(layout_style, available_height, available_height);
+
+                // Filling child of a shrinked container is measured by its own content first,
+                // then fills the content size of the container
+                let fill_width = layout_style.width == Length::Shrink
+                    && child.style.width.fill_portion().is_some();
+                let fill_height = layout_style.height == Length::Shrink
+                    && child.style.height.fill_portion().is_some();
+                let measured = (fill_width || fill_height).then(|| {
+                    let mut measured = child.style.clone();
+                    if fill_width {
+                        measured.width = Length::Shrink;
+                    }
+                    if fill_height {
+                        measured.height = Length::Shrink;
+                    }
+                    measured
+                });
+
+                // Layout the child inside the container, padding insets the child area
+                let child_x = parent_x + self.leading(padding.left, padding.right);
+                let child_y = parent_y + padding.top as f32;
+                let absolute = Self::is_absolute(child);
+                if !absolute {
+                    self.layout_node(
+                        child,
+                        measured.as_ref().unwrap_or(&child.style),
+                        child_x,
+                        child_y,
+                        (width - padding_width).max(0.0),
+                        (height - padding_height).max(0.0),
+                        font_cx,
+                        layout_cx,
+                    );
+                }
+
+                let (child_width, child_height) = if absolute {
+                    (0.0, 0.0)
+                } else {
+                    self.outer_size(child)
+                };
+                let container_width =
+                    Self::resolve_width(layout_style, available_width, child_width + padding_width);
+                let container_height = Self::resolve_height(
+                    layout_style,
+                    available_height,
+                    child_height + padding_height,
+                );
+                let (container_width, container_height) =
+                    Self::apply_aspect_ratio(layout_style, container_width, container_height);
+
+                if !absolute && measured.is_some() {
+                    self.resolve_node(
+                        child,
+                        child_x,
+                        child_y,
+                        (container_width - padding_width).max(0.0),
+                        (container_height - padding_height).max(0.0),
+                        font_cx,
+                        layout_cx,
+                    );
+                }
 
                 let container_node = LayoutNode {
                     x: parent_x,
@@ -116,26 +683,42 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
                 };
                 self.nodes.insert(widget.id, container_node);
 
-                // Layout the child inside the container
-                self.resolve_node(
-                    child,
-                    parent_x,
-                    parent_y,
-                    container_width,
-                    container_height,
-                    font_cx,
-                    layout_cx,
-                );
+                if absolute {
+                    self.place_absolute(
+                        child,
+                        parent_x,
+                        parent_y,
+                        container_width,
+                        container_height,
+                        font_cx,
+                        layout_cx,
+                    );
+                } else {
+                    self.align_child(
+                        child,
+                        (container_width - padding_width).max(0.0),
+                        (container_height - padding_height).max(0.0),
+                    );
+                }
             }
-            WidgetElement::Image { .. } => {
-                let width = widget.style.width as f32;
-                let height = widget.style.height as f32;
+            WidgetElement::Image { image, crop, .. } => {
+                // Cropped image takes the size of the shown part
+                let (image_width, image_height) =
+                    (image.image.width as f32, image.image.height as f32);
+                let (image_width, image_height) = match crop {
+                    Some(crop) => crop.size_inside(image_width, image_height),
+                    None => (image_width, image_height),
+                };
+                let width = Self::resolve_width(layout_style, available_width, image_width)
+                    .min(available_width);
+                let height = Self::resolve_height(layout_style, available_height, image_height);
+                let (width, height) = Self::apply_aspect_ratio(layout_style, width, height);
 
                 let image_node = LayoutNode {
                     x: parent_x,
                     y: parent_y,
-                    width: width.min(available_width),
-                    height: height,
+                    width,
+                    height,
                     parent_height: available_height,
                     parent_width: available_width,
                 };
@@ -146,38 +729,123 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
                 font_size,
                 weight,
                 style,
-                ..
+                color,
             } => {
-                let (width, height) = measure_text(
-                    font_cx,
+                let mut hasher = DefaultHasher::new();
+                (
                     content,
+                    font_size,
                     weight,
                     style,
-                    widget.style.spacing,
-                    *font_size as f32,
-                    1.0,
-                    layout_cx,
-                );
+                    color,
+                    layout_style.spacing,
+                    self.direction,
+                )
+                    .hash(&mut hasher);
 
-                let (x_offset, y_offset) = self.get_align_offset(
-                    available_height,
-                    available_width,
+                // Text longer than the widget can be wraps
+                let max_width = Self::resolve_width(layout_style, available_width, available_width);
+                let registred_fallback_font = self.registred_fallback_font;
+                let scale = self.scale;
+                let key = hasher.finish();
+                let layout = self.cached_text(widget.id, key, Some(max_width * scale), || {
+                    text_layout(
+                        font_cx,
+                        layout_cx,
+                        content,
+                        weight,
+                        style,
+                        *color,
+                        layout_style.spacing,
+                        *font_size as f32,
+                        scale,
+                        registred_fallback_font,
+                    )
+                });
+                // Shaped text is in device pixels
+                let (width, height) = (layout.width() / scale, layout.height() / scale);
+                let baseline = Self::first_baseline(layout) / scale;
+
+                let width = Self::resolve_width(layout_style, available_width, width);
+                let height = Self::resolve_height(layout_style, available_height, height);
+                if self.is_rtl() {
+                    self.align_text_right(widget.id, width * scale);
+                }
+
+                let text_node = LayoutNode {
+                    x: parent_x,
+                    y: parent_y,
                     width,
                     height,
-                    &widget.style.align,
-                );
+                    parent_height: available_height,
+                    parent_width: available_width,
+                };
+                self.nodes.insert(widget.id, text_node);
+                self.baselines.insert(widget.id, baseline);
+            }
+            WidgetElement::RichText { spans, .. } => {
+                // Text longer than the widget can be wraps
+                let max_width = Self::resolve_width(layout_style, available_width, available_width);
+                let mut hasher = DefaultHasher::new();
+                (spans, layout_style.spacing, self.direction).hash(&mut hasher);
+
+                let registred_fallback_font = self.registred_fallback_font;
+                let scale = self.scale;
+                let key = hasher.finish();
+                let layout = self.cached_text(widget.id, key, Some(max_width * scale), || {
+                    rich_text_layout(
+                        font_cx,
+                        layout_cx,
+                        spans,
+                        layout_style.spacing,
+                        scale,
+                        registred_fallback_font,
+                    )
+                });
+                // Shaped text is in device pixels
+                let (width, height) = (layout.width() / scale, layout.height() / scale);
+                let baseline = Self::first_baseline(layout) / scale;
+
+                let width = Self::resolve_width(layout_style, available_width, width);
+                let height = Self::resolve_height(layout_style, available_height, height);
+                if self.is_rtl() {
+                    self.align_text_right(widget.id, width * scale);
+                }
+                let links = match self.text_layout(widget.id) {
+                    Some(layout) => link_areas(layout, spans),
+                    None => Vec::new(),
+                };
+
+                let x = parent_x;
+                let y = parent_y;
+
+                // Store link areas with window coordinates for hit-testing
+                let links = links
+                    .into_iter()
+                    .map(|area| LinkArea {
+                        x: area.x / scale + x,
+                        y: area.y / scale + y,
+                        width: area.width / scale,
+                        height: area.height / scale,
+                        ..area
+                    })
+                    .collect();
+                self.links.insert(widget.id, links);
 
                 let text_node = LayoutNode {
-                    x: parent_x + x_offset,
-                    y: parent_y + y_offset,
-                    width: width.min(available_width),
-                    height: height,
+                    x,
+                    y,
+                    width,
+                    height,
                     parent_height: available_height,
                     parent_width: available_width,
                 };
                 self.nodes.insert(widget.id, text_node);
+                self.baselines.insert(widget.id, baseline);
             }
         }
+
+        self.direction = parent_direction;
     }
 
     /// Get x and y offset for align
@@ -213,145 +881,691 @@ impl<M: Clone + Send + 'static> LayoutEngine<M> {
         (x_offset, y_offset)
     }
 
-    /// Layout children vertically (VStack)
-    fn layout_vstack(
+    /// Cross axis alignment of a stack child
+    fn cross_align(style: &Style, child: &Widget<M>) -> CrossAlign {
+        child.style.align_self.unwrap_or(style.cross_align)
+    }
+
+    /// Layout children of a stack along the axis
+    fn layout_stack(
         &mut self,
         widget_id: u64,
         style: &Style,
         children: &Vec<Widget<M>>,
+        axis: Axis,
         parent_x: f32,
         parent_y: f32,
-        available_height: f32,
         available_width: f32,
-        spacing: f32,
+        available_height: f32,
         font_cx: &mut FontContext,
-        layout_cx: &mut LayoutContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
     ) {
-        let (x_offset, y_offset) =
-            self.get_align_offset(available_height, available_width, 0.0, 0.0, &style.align);
+        let spacing = style.spacing as f32;
+        let padding = &style.padding;
+        let padding_width = (padding.left + padding.right) as f32;
+        let padding_height = (padding.top + padding.bottom) as f32;
+        let content_x = parent_x + self.leading(padding.left, padding.right);
+        let content_y = parent_y + padding.top as f32;
+
+        // Absolute children are placed after the stack, outside of its flow
+        let (absolute, children): (Vec<&Widget<M>>, Vec<&Widget<M>>) =
+            children.iter().partition(|child| Self::is_absolute(child));
 
-        let mut current_y = parent_y + y_offset;
-        let current_x = parent_x + x_offset;
-        let mut total_height = 0.0;
-        let mut max_width = 0.0;
+        // Space for children, shrinked stack can use all available space
+        let inner_width =
+            (Self::resolve_width(style, available_width, available_width) - padding_width).max(0.0);
+        let inner_height = (Self::resolve_height(style, available_height, available_height)
+            - padding_height)
+            .max(0.0);
+        let (inner_main, inner_cross) = axis.main_cross(inner_width, inner_height);
 
-        // Layout all children
-        for child in children {
-            // Layout the child
+        let total_spacing = spacing * children.len().saturating_sub(1) as f32;
+        let mut used_main = total_spacing;
+        let mut portions = 0;
+        // Main size each child was laid out in
+        let mut available_main = vec![inner_main; children.len()];
+
+        // Layout children with own main size first
+        for &child in &children {
+            if let Some(portion) = axis.main_length(&child.style).fill_portion() {
+                portions += portion as u32;
+                continue;
+            }
             self.resolve_node(
-                &child,
-                current_x,
-                current_y,
-                available_width,
-                available_height,
+                child,
+                content_x,
+                content_y,
+                inner_width,
+                inner_height,
                 font_cx,
                 layout_cx,
             );
+            let (width, height) = self.outer_size(child);
+            used_main += axis.main_cross(width, height).0;
+        }
+
+        // Filling children share the main size that is left
+        let remaining_main = (inner_main - used_main).max(0.0);
+        for (index, child) in children.iter().copied().enumerate() {
+            if let Some(portion) = axis.main_length(&child.style).fill_portion() {
+                available_main[index] = remaining_main * portion as f32 / portions.max(1) as f32;
+                let (width, height) = axis.width_height(available_main[index], inner_cross);
+                self.resolve_node(
+                    child, content_x, content_y, width, height, font_cx, layout_cx,
+                );
+            }
+        }
 
-            // Get the child computed height
-            if let Some(child_node) = self.nodes.get(&child.id) {
-                let child_height = child_node.height;
+        // Style a child was laid out with, when it differs from its own
+        let mut layout_styles: Vec<Option<Style>> = vec![None; children.len()];
 
-                current_y += child_height + spacing;
-                total_height += child_height + spacing;
+        // Grow children into the space left, or shrink them when children overflow
+        let child_mains: Vec<f32> = children
+            .iter()
+            .map(|child| {
+                let (width, height) = self.outer_size(child);
+                axis.main_cross(width, height).0
+            })
+            .collect();
+        let free_main = inner_main - total_spacing - child_mains.iter().sum::<f32>();
+        // Shrinked stack has no space left to grow into, it is as long as its children
+        let definite_main = *axis.main_length(style) != Length::Shrink;
+        let weights: Vec<f32> = children
+            .iter()
+            .zip(&child_mains)
+            .map(|(child, child_main)| {
+                if free_main > 0.0 && definite_main {
+                    child.style.grow
+                } else if free_main < 0.0 {
+                    // Like CSS, bigger children give back more
+                    child.style.shrink * child_main
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let total_weight: f32 = weights.iter().sum();
 
-                if child_node.width > max_width {
-                    max_width = child_node.width;
+        if total_weight > 0.0 {
+            for (index, child) in children.iter().copied().enumerate() {
+                if weights[index] <= 0.0 {
+                    continue;
                 }
+                available_main[index] =
+                    (child_mains[index] + free_main * weights[index] / total_weight).max(0.0);
+
+                // Child fills the main size it was given
+                let mut flexed = child.style.clone();
+                *axis.main_length_mut(&mut flexed) = Length::Fill;
+
+                let (width, height) = axis.width_height(available_main[index], inner_cross);
+                self.layout_node(
+                    child, &flexed, content_x, content_y, width, height, font_cx, layout_cx,
+                );
+                layout_styles[index] = Some(flexed);
             }
         }
-        // Remove last spacing
-        if !children.is_empty() {
-            total_height -= spacing;
+
+        // Children aligned on baseline are moved down to the lowest first baseline
+        let baseline_aligned = |child: &Widget<M>| {
+            axis == Axis::Horizontal && Self::cross_align(style, child) == CrossAlign::Baseline
+        };
+        let max_baseline = children
+            .iter()
+            .filter(|&child| baseline_aligned(child))
+            .map(|child| child.style.margin.top as f32 + self.baseline(child))
+            .fold(0.0, f32::max);
+
+        let mut total_main = total_spacing;
+        let mut max_cross: f32 = 0.0;
+        for &child in &children {
+            let (width, height) = self.outer_size(child);
+            let (child_main, mut child_cross) = axis.main_cross(width, height);
+            if baseline_aligned(child) {
+                child_cross += max_baseline - child.style.margin.top as f32 - self.baseline(child);
+            }
+            total_main += child_main;
+            max_cross = max_cross.max(child_cross);
+        }
+
+        let (content_width, content_height) = axis.width_height(total_main, max_cross);
+        let width = Self::resolve_width(style, available_width, content_width + padding_width);
+        let height = Self::resolve_height(style, available_height, content_height + padding_height);
+        let (width, height) = Self::apply_aspect_ratio(style, width, height);
+        let (main_size, cross_size) = axis.main_cross(
+            (width - padding_width).max(0.0),
+            (height - padding_height).max(0.0),
+        );
+
+        // Stretched children are laid out again with the cross size of the stack
+        for (index, child) in children.iter().copied().enumerate() {
+            let mut stretched = layout_styles[index]
+                .clone()
+                .unwrap_or_else(|| child.style.clone());
+            let cross_length = axis.cross_length_mut(&mut stretched);
+            if Self::cross_align(style, child) != CrossAlign::Stretch
+                || *cross_length != Length::Shrink
+            {
+                continue;
+            }
+            *cross_length = Length::Fill;
+
+            let (width, height) = axis.width_height(available_main[index], cross_size);
+            self.layout_node(
+                child, &stretched, content_x, content_y, width, height, font_cx, layout_cx,
+            );
         }
 
-        for child in children {
+        // Space left on the main axis is placed by justify
+        let count = children.len() as f32;
+        let free = (main_size - total_main).max(0.0);
+        let (start, gap) = match style.justify {
+            Justify::Start => (0.0, 0.0),
+            Justify::Center => (free / 2.0, 0.0),
+            Justify::End => (free, 0.0),
+            Justify::SpaceBetween if children.len() > 1 => (0.0, free / (count - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround if !children.is_empty() => (free / count / 2.0, free / count),
+            Justify::SpaceAround => (0.0, 0.0),
+            Justify::SpaceEvenly => (free / (count + 1.0), free / (count + 1.0)),
+        };
+
+        // Place children after each other
+        let mut current_main = start;
+        for &child in &children {
+            let (width, height) = self.outer_size(child);
+            let (child_main, child_cross) = axis.main_cross(width, height);
+
+            let mut cross_offset = match Self::cross_align(style, child) {
+                CrossAlign::Start | CrossAlign::Stretch => 0.0,
+                CrossAlign::Center => (cross_size - child_cross) / 2.0,
+                CrossAlign::End => cross_size - child_cross,
+                CrossAlign::Baseline if axis == Axis::Horizontal => {
+                    max_baseline - child.style.margin.top as f32 - self.baseline(child)
+                }
+                CrossAlign::Baseline => 0.0,
+            };
+
+            // Right to left, rows start at the right and columns align to the right
+            let mut main_offset = current_main;
+            if self.is_rtl() {
+                match axis {
+                    Axis::Horizontal => main_offset = main_size - current_main - child_main,
+                    Axis::Vertical => cross_offset = cross_size - child_cross - cross_offset,
+                }
+            }
+
+            let (x, y) = axis.width_height(main_offset, cross_offset);
+            self.translate(child, x, y);
+            current_main += child_main + spacing + gap;
+
             if let Some(child_node) = self.nodes.get_mut(&child.id) {
-                child_node.parent_width = max_width;
-                child_node.parent_height = total_height;
+                child_node.parent_width = content_width;
+                child_node.parent_height = content_height;
             }
         }
 
-        // Store the VStack own node
-        let vstack_node = LayoutNode {
+        // Store the stack own node
+        let stack_node = LayoutNode {
             x: parent_x,
             y: parent_y,
-            width: max_width,
-            height: total_height,
+            width,
+            height,
             parent_height: available_height,
             parent_width: available_width,
         };
-        self.nodes.insert(widget_id, vstack_node);
+        self.nodes.insert(widget_id, stack_node);
+
+        for child in absolute {
+            self.place_absolute(child, parent_x, parent_y, width, height, font_cx, layout_cx);
+        }
     }
 
-    /// Layout children horizontally (HStack)
-    fn layout_hstack(
+    /// Layout children in rows, starting a new row when the next child is wider than the space left
+    fn layout_wrap(
         &mut self,
         widget_id: u64,
         style: &Style,
         children: &Vec<Widget<M>>,
+        vertical_spacing: f32,
         parent_x: f32,
         parent_y: f32,
-        available_height: f32,
         available_width: f32,
-        spacing: f32,
+        available_height: f32,
         font_cx: &mut FontContext,
-        layout_cx: &mut LayoutContext,
+        layout_cx: &mut LayoutContext<TextBrush>,
     ) {
-        let (x_offset, y_offset) =
-            self.get_align_offset(available_height, available_width, 0.0, 0.0, &style.align);
+        let spacing = style.spacing as f32;
+        let padding = &style.padding;
+        let padding_width = (padding.left + padding.right) as f32;
+        let padding_height = (padding.top + padding.bottom) as f32;
+        let content_x = parent_x + self.leading(padding.left, padding.right);
+        let content_y = parent_y + padding.top as f32;
+
+        // Absolute children are placed after the wrap, outside of its rows
+        let (absolute, children): (Vec<&Widget<M>>, Vec<&Widget<M>>) =
+            children.iter().partition(|child| Self::is_absolute(child));
+
+        // Rows are as wide as the wrap can be
+        let inner_width =
+            (Self::resolve_width(style, available_width, available_width) - padding_width).max(0.0);
+        let inner_height = (Self::resolve_height(style, available_height, available_height)
+            - padding_height)
+            .max(0.0);
 
-        let mut current_x = parent_x + x_offset;
-        let current_y = parent_y + y_offset;
-        let mut total_width = 0.0;
-        let mut max_height = 0.0;
+        // Children of every row
+        let mut rows: Vec<Vec<&Widget<M>>> = Vec::new();
+        let mut row_width = 0.0;
 
-        // Layout all children
-        for child in children {
-            // Layout the child
+        for &child in &children {
             self.resolve_node(
-                &child,
-                current_x,
-                current_y,
-                available_width,
-                available_height,
+                child,
+                content_x,
+                content_y,
+                inner_width,
+                inner_height,
                 font_cx,
                 layout_cx,
             );
+            let (child_width, _) = self.outer_size(child);
 
-            // Get the child computed width
-            if let Some(child_node) = self.nodes.get(&child.id) {
-                let child_width = child_node.width;
+            match rows.last_mut() {
+                Some(row) if row_width + spacing + child_width <= inner_width => {
+                    row.push(child);
+                    row_width += spacing + child_width;
+                }
+                // First child or the child doesn't fit, start a new row
+                _ => {
+                    rows.push(vec![child]);
+                    row_width = child_width;
+                }
+            }
+        }
 
-                current_x += child_width + spacing;
-                total_width += child_width + spacing;
+        // Position of every child in the rows, applied once the wrap width is known
+        let mut placements: Vec<(&Widget<M>, f32, f32, f32)> = Vec::new();
 
-                if child_node.height > max_height {
-                    max_height = child_node.height;
-                }
+        // Place rows below each other
+        let mut current_y = 0.0;
+        let mut max_width: f32 = 0.0;
+        for row in &rows {
+            let row_height = row
+                .iter()
+                .map(|child| self.outer_size(child).1)
+                .fold(0.0, f32::max);
+            let max_baseline = row
+                .iter()
+                .map(|child| child.style.margin.top as f32 + self.baseline(child))
+                .fold(0.0, f32::max);
+
+            let mut current_x = 0.0;
+            for child in row {
+                let (child_width, child_height) = self.outer_size(child);
+                let y_offset = match Self::cross_align(style, child) {
+                    CrossAlign::Start | CrossAlign::Stretch => 0.0,
+                    CrossAlign::Center => (row_height - child_height) / 2.0,
+                    CrossAlign::End => row_height - child_height,
+                    CrossAlign::Baseline => {
+                        max_baseline - child.style.margin.top as f32 - self.baseline(child)
+                    }
+                };
+
+                placements.push((child, current_x, current_y + y_offset, child_width));
+                current_x += child_width + spacing;
             }
+
+            max_width = max_width.max(current_x - spacing);
+            current_y += row_height + vertical_spacing;
         }
         // Remove last spacing
-        if !children.is_empty() {
-            total_width -= spacing;
-        }
+        let total_height = if rows.is_empty() {
+            0.0
+        } else {
+            current_y - vertical_spacing
+        };
 
-        for child in children {
+        for &child in &children {
             if let Some(child_node) = self.nodes.get_mut(&child.id) {
-                child_node.parent_width = total_width;
-                child_node.parent_height = max_height;
+                child_node.parent_width = max_width;
+                child_node.parent_height = total_height;
             }
         }
 
-        // Store the HStack own node
-        let hstack_node = LayoutNode {
+        // Store the wrap own node
+        let width = Self::resolve_width(style, available_width, max_width + padding_width);
+        let height = Self::resolve_height(style, available_height, total_height + padding_height);
+        let (width, height) = Self::apply_aspect_ratio(style, width, height);
+        let wrap_node = LayoutNode {
             x: parent_x,
             y: parent_y,
-            width: total_width,
-            height: max_height,
+            width,
+            height,
             parent_height: available_height,
             parent_width: available_width,
         };
-        self.nodes.insert(widget_id, hstack_node);
+        self.nodes.insert(widget_id, wrap_node);
+
+        // Right to left, rows start at the right edge
+        let row_size = (width - padding_width).max(0.0);
+        for (child, x, y, child_width) in placements {
+            let x = if self.is_rtl() {
+                row_size - x - child_width
+            } else {
+                x
+            };
+            self.translate(child, x, y);
+        }
+
+        for child in absolute {
+            self.place_absolute(child, parent_x, parent_y, width, height, font_cx, layout_cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glazeui_core::{
+        Absolute, Direction, Margin, Padding, Rect,
+        image_handle::ImageHandle,
+        widget::{
+            container::Container,
+            hstack::HStack,
+            image_widget::ImageWidget,
+            rich_text::{RichText, Span},
+            spacer::Spacer,
+            vstack::VStack,
+            wrap::Wrap,
+        },
+    };
+    use parley::fontique::Blob;
+
+    use super::*;
+
+    fn spacer(width: f32, height: f32) -> Widget<()> {
+        Spacer::new().width(width).height(height).build()
+    }
+
+    fn compute(root: &Widget<()>, width: f32, height: f32) -> LayoutEngine<()> {
+        let mut layout = LayoutEngine::new();
+        layout.compute(
+            root,
+            width,
+            height,
+            1.0,
+            false,
+            &mut FontContext::new(),
+            &mut LayoutContext::new(),
+        );
+        layout
+    }
+
+    /// Layout with only the bundled Inter font, so text is shaped the same on every machine
+    fn compute_text(root: &Widget<()>, width: f32, height: f32) -> LayoutEngine<()> {
+        let mut font_context = FontContext::new();
+        let font = include_bytes!("../../../src/assets/fonts/Inter.ttf").to_vec();
+        font_context
+            .collection
+            .register_fonts(Blob::from(font), None);
+
+        let mut layout = LayoutEngine::new();
+        layout.compute(
+            root,
+            width,
+            height,
+            1.0,
+            true,
+            &mut font_context,
+            &mut LayoutContext::new(),
+        );
+        layout
+    }
+
+    fn read_more() -> Widget<()> {
+        RichText::new(vec![
+            Span::new("Read ".to_string()),
+            Span::new("more".to_string()).link("https://example.com"),
+        ])
+        .build()
+    }
+
+    /// Position and size of the laid out widget
+    fn rect(layout: &LayoutEngine<()>, widget: &Widget<()>) -> (f32, f32, f32, f32) {
+        let node = layout.get(widget.id).unwrap();
+        (node.x, node.y, node.width, node.height)
+    }
+
+    fn children(widget: &Widget<()>) -> &[Widget<()>] {
+        match &widget.element {
+            WidgetElement::VStack { children }
+            | WidgetElement::HStack { children }
+            | WidgetElement::Wrap { children, .. } => children,
+            WidgetElement::Container { child, .. } => std::slice::from_ref(child.as_ref()),
+            _ => &[],
+        }
+    }
+
+    #[test]
+    fn top_margin_moves_child_down() {
+        let child = Container::new(spacer(10.0, 10.0))
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .margin(Margin::new().top(5).left(3))
+            .build();
+        let root = Container::new(VStack::new(vec![child]).build())
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .padding(Padding::all(2))
+            .build();
+        let layout = compute(&root, 100.0, 100.0);
+
+        let stack = &children(&root)[0];
+        assert_eq!(rect(&layout, &children(stack)[0]), (5.0, 7.0, 10.0, 10.0));
+        // Margin is part of the parent size
+        assert_eq!(rect(&layout, &root), (0.0, 0.0, 17.0, 19.0));
+    }
+
+    #[test]
+    fn fill_child_of_shrinked_container() {
+        let root = Container::new(
+            Container::new(spacer(20.0, 10.0))
+                .width(Length::Fill)
+                .height(Length::Shrink)
+                .build(),
+        )
+        .width(Length::Shrink)
+        .height(Length::Shrink)
+        .build()
+        .min_width(120);
+        let layout = compute(&root, 300.0, 100.0);
+
+        // Child fills the content of the container, not the window
+        assert_eq!(rect(&layout, &root).2, 120.0);
+        assert_eq!(rect(&layout, &children(&root)[0]).2, 120.0);
+    }
+
+    #[test]
+    fn grow_splits_free_space() {
+        let root = HStack::new(vec![
+            spacer(50.0, 10.0).grow(1.0),
+            spacer(50.0, 10.0).grow(3.0),
+            spacer(20.0, 10.0),
+        ])
+        .width(200)
+        .build();
+        let layout = compute(&root, 300.0, 100.0);
+
+        let [first, second, third] = children(&root) else {
+            unreachable!()
+        };
+        assert_eq!(rect(&layout, first), (0.0, 0.0, 70.0, 10.0));
+        assert_eq!(rect(&layout, second), (70.0, 0.0, 110.0, 10.0));
+        assert_eq!(rect(&layout, third), (180.0, 0.0, 20.0, 10.0));
+    }
+
+    #[test]
+    fn grow_needs_definite_length() {
+        let root = HStack::new(vec![spacer(50.0, 10.0).grow(1.0)]).build();
+        let layout = compute(&root, 300.0, 100.0);
+
+        assert_eq!(rect(&layout, &root).2, 50.0);
+    }
+
+    #[test]
+    fn wrap_breaks_rows() {
+        let root = Wrap::new(vec![
+            spacer(40.0, 10.0),
+            spacer(40.0, 20.0),
+            spacer(40.0, 10.0),
+        ])
+        .spacing(10)
+        .width(100)
+        .build();
+        let layout = compute(&root, 300.0, 100.0);
+
+        let [first, second, third] = children(&root) else {
+            unreachable!()
+        };
+        assert_eq!(rect(&layout, first), (0.0, 0.0, 40.0, 10.0));
+        assert_eq!(rect(&layout, second), (50.0, 0.0, 40.0, 20.0));
+        // Third child doesn't fit next to the others
+        assert_eq!(rect(&layout, third), (0.0, 30.0, 40.0, 10.0));
+        assert_eq!(rect(&layout, &root), (0.0, 0.0, 100.0, 40.0));
+    }
+
+    #[test]
+    fn absolute_right_bottom() {
+        let badge = spacer(20.0, 10.0).position(Absolute {
+            right: Some(5.0),
+            bottom: Some(4.0),
+            ..Default::default()
+        });
+        let root = VStack::new(vec![spacer(200.0, 100.0), badge])
+            .margin(Margin::new().left(10).top(10))
+            .build();
+        let layout = compute(&root, 300.0, 300.0);
+
+        assert_eq!(rect(&layout, &root), (10.0, 10.0, 200.0, 100.0));
+        assert_eq!(
+            rect(&layout, &children(&root)[1]),
+            (185.0, 96.0, 20.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn aspect_ratio_gives_way_to_set_size() {
+        let root = Container::new(spacer(0.0, 0.0))
+            .width(Length::Shrink)
+            .height(100)
+            .aspect_ratio(2.0)
+            .build()
+            .max_width(150);
+        let layout = compute(&root, 300.0, 300.0);
+
+        // Width is clamped, the set height stays
+        assert_eq!(rect(&layout, &root), (0.0, 0.0, 150.0, 100.0));
+    }
+
+    #[test]
+    fn aspect_ratio_is_kept_when_clamped() {
+        let root = Container::new(spacer(100.0, 0.0))
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .aspect_ratio(0.5)
+            .build()
+            .max_height(120);
+        let layout = compute(&root, 300.0, 300.0);
+
+        // Height of 200 derived from the content width is clamped to 120, the width follows it
+        assert_eq!(rect(&layout, &root), (0.0, 0.0, 60.0, 120.0));
+    }
+
+    #[test]
+    fn rtl_mirrors_hstack() {
+        let root = HStack::new(vec![spacer(20.0, 10.0), spacer(30.0, 10.0)])
+            .spacing(5)
+            .width(100)
+            .build()
+            .direction(Direction::Rtl);
+        let layout = compute(&root, 300.0, 100.0);
+
+        let [first, second] = children(&root) else {
+            unreachable!()
+        };
+        assert_eq!(rect(&layout, first), (80.0, 0.0, 20.0, 10.0));
+        assert_eq!(rect(&layout, second), (45.0, 0.0, 30.0, 10.0));
+    }
+
+    #[test]
+    fn rtl_mirrors_absolute_children() {
+        let start = spacer(10.0, 10.0).position(Absolute {
+            left: Some(5.0),
+            top: Some(2.0),
+            ..Default::default()
+        });
+        let end = spacer(20.0, 10.0).position(Absolute {
+            right: Some(5.0),
+            ..Default::default()
+        });
+        let root = HStack::new(vec![spacer(30.0, 10.0), start, end])
+            .width(100)
+            .height(50)
+            .build()
+            .direction(Direction::Rtl);
+        let layout = compute(&root, 300.0, 100.0);
+
+        let [_, start, end] = children(&root) else {
+            unreachable!()
+        };
+        assert_eq!(rect(&layout, start), (85.0, 2.0, 10.0, 10.0));
+        assert_eq!(rect(&layout, end), (5.0, 0.0, 20.0, 10.0));
+    }
+
+    #[test]
+    fn link_at_finds_link_run() {
+        let root = read_more();
+        let layout = compute_text(&root, 300.0, 100.0);
+        let (x, y, width, height) = rect(&layout, &root);
+
+        let area = &layout.links[&root.id][0];
+        assert_eq!(area.span, 1);
+        // Link starts after "Read "
+        assert!(area.x > x);
+        let (center_x, center_y) = (area.x + area.width / 2.0, area.y + area.height / 2.0);
+        assert_eq!(layout.link_at(center_x, center_y), Some((root.id, 1)));
+
+        // Text before the link and space after the text aren't links
+        assert_eq!(layout.link_at(x + 1.0, center_y), None);
+        assert_eq!(layout.link_at(x + width + 10.0, center_y), None);
+        assert_eq!(layout.link_at(center_x, y + height + 10.0), None);
+    }
+
+    #[test]
+    fn link_at_finds_top_link() {
+        let below = read_more();
+        let above = read_more().position(Absolute {
+            left: Some(0.0),
+            top: Some(0.0),
+            ..Default::default()
+        });
+        let root = VStack::new(vec![below, above]).build();
+        let layout = compute_text(&root, 300.0, 100.0);
+
+        let above = &children(&root)[1];
+        let area = &layout.links[&above.id][0];
+        let (center_x, center_y) = (area.x + area.width / 2.0, area.y + area.height / 2.0);
+        assert_eq!(layout.link_at(center_x, center_y), Some((above.id, 1)));
+    }
+
+    #[test]
+    fn cropped_image_takes_crop_size() {
+        let handle = ImageHandle::from_rgba(40, 20, vec![255; 40 * 20 * 4]);
+        handle.load_blocking().unwrap();
+        let root = ImageWidget::new()
+            .handle(&handle, None, None)
+            .crop(Rect::new(5.0, 0.0, 10.0, 20.0))
+            .build();
+        let layout = compute(&root, 300.0, 300.0);
+
+        assert_eq!(rect(&layout, &root), (0.0, 0.0, 10.0, 20.0));
     }
 }
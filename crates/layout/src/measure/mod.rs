@@ -1 +1,2 @@
+pub mod rich_text;
 pub mod text;
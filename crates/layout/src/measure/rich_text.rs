@@ -0,0 +1,123 @@
+use glazeui_core::{TextStyle, widget::rich_text::TextSpan};
+use parley::{
+    FontContext, FontFamily, FontStack, FontWeight, GenericFamily, Layout, LayoutContext,
+    LineHeight, PositionedLayoutItem, StyleProperty,
+};
+
+use crate::measure::text::{TextBrush, font_weight};
+
+/// Area of a link span, relative to the top left corner of the text
+#[derive(Clone, Debug)]
+pub struct LinkArea {
+    pub span: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Shape all spans into a parley layout, lines are broken by the caller
+pub fn rich_text_layout(
+    font_cx: &mut FontContext,
+    layout_cx: &mut LayoutContext<TextBrush>,
+    spans: &[TextSpan],
+    text_spacing: i32,
+    scale: f32,
+    registred_fallback_font: bool,
+) -> Layout<TextBrush> {
+    let text: String = spans.iter().map(|span| span.content.as_str()).collect();
+
+    // Create a RangedBuilder
+    let mut builder = layout_cx.ranged_builder(font_cx, &text, scale, true);
+
+    // Set default font family
+    builder.push_default(GenericFamily::SystemUi);
+    builder.push_default(LineHeight::FontSizeRelative(1.3));
+    builder.push_default(StyleProperty::LetterSpacing(text_spacing as f32));
+
+    if registred_fallback_font {
+        builder.push_default(StyleProperty::FontStack(FontStack::Single(
+            FontFamily::Named("Inter".into()),
+        )));
+    }
+
+    let mut start = 0;
+    for (index, span) in spans.iter().enumerate() {
+        let range = start..start + span.content.len();
+        start = range.end;
+
+        let color = span.color;
+
+        let style = match span.style {
+            TextStyle::Italic | TextStyle::ItalicStriketrough => parley::FontStyle::Italic,
+            _ => parley::FontStyle::Normal,
+        };
+
+        builder.push(
+            StyleProperty::Brush(TextBrush {
+                color: [color.0, color.1, color.2, color.3],
+                span: index,
+            }),
+            range.clone(),
+        );
+        builder.push(
+            StyleProperty::FontWeight(FontWeight::new(font_weight(&span.weight))),
+            range.clone(),
+        );
+        builder.push(StyleProperty::FontStyle(style), range.clone());
+        if span.code {
+            builder.push(
+                StyleProperty::FontStack(FontStack::Single(FontFamily::Generic(
+                    GenericFamily::Monospace,
+                ))),
+                range.clone(),
+            );
+        }
+        builder.push(
+            StyleProperty::FontSize(span.font_size as f32),
+            range.clone(),
+        );
+        builder.push(
+            StyleProperty::Underline(matches!(span.style, TextStyle::Underline)),
+            range.clone(),
+        );
+        builder.push(
+            StyleProperty::Strikethrough(matches!(
+                span.style,
+                TextStyle::Striketrough | TextStyle::ItalicStriketrough
+            )),
+            range,
+        );
+    }
+
+    // Build the builder into a Layout
+    builder.build(&text)
+}
+
+/// Get the areas of link spans from glyph runs of the layout
+pub fn link_areas(layout: &Layout<TextBrush>, spans: &[TextSpan]) -> Vec<LinkArea> {
+    let mut areas = Vec::new();
+
+    for line in layout.lines() {
+        let metrics = line.metrics();
+        for item in line.items() {
+            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+            let span = glyph_run.style().brush.span;
+            if spans.get(span).is_none_or(|span| span.link.is_none()) {
+                continue;
+            }
+
+            areas.push(LinkArea {
+                span,
+                x: glyph_run.offset(),
+                y: metrics.min_coord,
+                width: glyph_run.advance(),
+                height: metrics.max_coord - metrics.min_coord,
+            });
+        }
+    }
+
+    areas
+}
@@ -1,22 +1,18 @@
 use glazeui_core::{TextStyle, TextWeight};
 use parley::{
-    FontContext, FontWeight, GenericFamily, Layout, LayoutContext, LineHeight, StyleProperty,
+    FontContext, FontFamily, FontStack, FontWeight, GenericFamily, Layout, LayoutContext,
+    LineHeight, StyleProperty,
 };
 
-pub fn measure_text(
-    font_cx: &mut FontContext,
-    text: &str,
-    text_weight: &TextWeight,
-    text_style: &TextStyle,
-    text_spacing: i32,
-    font_size: f32,
-    scale: f32,
-    layout_cx: &mut LayoutContext,
-) -> (f32, f32) {
-    // Create a RangedBuilder
-    let mut builder = layout_cx.ranged_builder(font_cx, &text, scale, true);
+/// Brush of a text glyph run, keeps the index of the span so runs can be mapped back
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextBrush {
+    pub color: [u8; 4],
+    pub span: usize,
+}
 
-    let weight = match text_weight {
+pub fn font_weight(text_weight: &TextWeight) -> f32 {
+    match text_weight {
         TextWeight::THIN => 100.0,
         TextWeight::EXTRALIGHT => 200.0,
         TextWeight::LIGHT => 300.0,
@@ -26,16 +22,35 @@ pub fn measure_text(
         TextWeight::BOLD => 700.0,
         TextWeight::EXTRABOLD => 800.0,
         TextWeight::BLACK => 900.0,
-    };
+    }
+}
+
+/// Shape the text into a parley layout, lines are broken by the caller
+pub fn text_layout(
+    font_cx: &mut FontContext,
+    layout_cx: &mut LayoutContext<TextBrush>,
+    text: &str,
+    text_weight: &TextWeight,
+    text_style: &TextStyle,
+    text_color: (u8, u8, u8, u8),
+    text_spacing: i32,
+    font_size: f32,
+    scale: f32,
+    registred_fallback_font: bool,
+) -> Layout<TextBrush> {
+    // Create a RangedBuilder
+    let mut builder = layout_cx.ranged_builder(font_cx, &text, scale, true);
+
+    let weight = font_weight(text_weight);
 
     let style = match text_style {
-        TextStyle::Italic => parley::FontStyle::Italic,
+        TextStyle::Italic | TextStyle::ItalicStriketrough => parley::FontStyle::Italic,
         TextStyle::Normal => parley::FontStyle::Normal,
         _ => parley::FontStyle::Normal,
     };
 
     let striketrough = match text_style {
-        TextStyle::Striketrough => true,
+        TextStyle::Striketrough | TextStyle::ItalicStriketrough => true,
         _ => false,
     };
 
@@ -46,18 +61,31 @@ pub fn measure_text(
 
     // Set default font family
     builder.push_default(GenericFamily::SystemUi);
+    // Set default text colour styles
+    builder.push_default(StyleProperty::Brush(TextBrush {
+        color: [text_color.0, text_color.1, text_color.2, text_color.3],
+        span: 0,
+    }));
+
+    // Set font weight
     builder.push_default(StyleProperty::FontWeight(FontWeight::new(weight)));
+    // Set font style (Italic, Normal)
     builder.push_default(StyleProperty::FontStyle(style));
+    // Set line height (font size * 1.3)
     builder.push_default(LineHeight::FontSizeRelative(1.3));
+    // Set font size
     builder.push_default(StyleProperty::FontSize(font_size));
+
     builder.push_default(StyleProperty::Strikethrough(striketrough));
     builder.push_default(StyleProperty::Underline(underline));
     builder.push_default(StyleProperty::LetterSpacing(text_spacing as f32));
 
-    // Build the builder into a Layout
-    let mut layout: Layout<[u8; 4]> = builder.build(&text);
-    layout.break_all_lines(None);
+    if registred_fallback_font {
+        builder.push_default(StyleProperty::FontStack(FontStack::Single(
+            FontFamily::Named("Inter".into()),
+        )));
+    }
 
-    // Return text size
-    return (layout.width(), layout.height());
+    // Build the builder into a Layout
+    builder.build(&text)
 }
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Instant;
+
+use glazeui_core::{Background, Direction, Widget, WidgetElement};
+use glazeui_layout::LayoutEngine;
+use kurbo::{Affine, Rect};
+
+use crate::{transform::widget_affine, widgets::draw_shadow::drop_shadows_bounds};
+
+/// Part of the window that changed since the last frame, in layout pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Damage {
+    /// Nothing changed, the last frame can stay on screen
+    None,
+    /// Only this area changed
+    Area(Rect),
+    /// Everything has to be drawn again
+    Full,
+}
+
+/// Remembers where every widget was painted and how it looked, to find what changed between frames
+///
+/// Widget ids start from zero every frame, so the same view gives the same ids.
+pub struct DamageTracker {
+    /// Painted bounds and fingerprint of every widget that draws something
+    widgets: HashMap<u64, (Rect, u64)>,
+    background: u64,
+    full: bool,
+}
+
+impl Default for DamageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self {
+            widgets: HashMap::new(),
+            background: 0,
+            full: true,
+        }
+    }
+
+    /// Draw everything on the next frame, e.g. after the window was resized
+    pub fn invalidate(&mut self) {
+        self.full = true;
+    }
+
+    /// Compare the laid out widgets with the last frame and remember them for the next one
+    ///
+    /// `time` is the time of the frame, animated images are compared by the frame shown at it
+    pub fn update<M: Clone + Send + 'static>(
+        &mut self,
+        layout_engine: &LayoutEngine<M>,
+        hovered_link: Option<(u64, usize)>,
+        widget: &Widget<M>,
+        background: &Background,
+        time: Instant,
+    ) -> Damage {
+        let mut widgets = HashMap::with_capacity(self.widgets.len());
+        collect(
+            layout_engine,
+            hovered_link,
+            widget,
+            Affine::IDENTITY,
+            1.0,
+            layout_engine.base_direction(),
+            time,
+            &mut widgets,
+        );
+
+        let mut hasher = DefaultHasher::new();
+        background.hash(&mut hasher);
+        let background = hasher.finish();
+
+        let damage = if self.full || background != self.background {
+            Damage::Full
+        } else {
+            let mut area: Option<Rect> = None;
+            let mut add = |bounds: Rect| {
+                area = Some(match area {
+                    Some(area) => area.union(bounds),
+                    None => bounds,
+                });
+            };
+
+            for (id, (bounds, fingerprint)) in &widgets {
+                match self.widgets.get(id) {
+                    Some((old_bounds, old_fingerprint))
+                        if old_bounds == bounds && old_fingerprint == fingerprint => {}
+                    Some((old_bounds, _)) => {
+                        add(*old_bounds);
+                        add(*bounds);
+                    }
+                    None => add(*bounds),
+                }
+            }
+            // Widgets that are gone leave their old area behind
+            for (id, (old_bounds, _)) in &self.widgets {
+                if !widgets.contains_key(id) {
+                    add(*old_bounds);
+                }
+            }
+
+            match area {
+                Some(area) => Damage::Area(area),
+                None => Damage::None,
+            }
+        };
+
+        self.widgets = widgets;
+        self.background = background;
+        self.full = false;
+        damage
+    }
+}
+
+/// Save the painted bounds and the fingerprint of the widget and its children
+fn collect<M: Clone + Send + 'static>(
+    layout_engine: &LayoutEngine<M>,
+    hovered_link: Option<(u64, usize)>,
+    widget: &Widget<M>,
+    transform: Affine,
+    opacity: f32,
+    direction: Direction,
+    time: Instant,
+    widgets: &mut HashMap<u64, (Rect, u64)>,
+) {
+    let Some(node) = layout_engine.get(widget.id) else {
+        return;
+    };
+
+    // Same transform and opacity as when the widget is drawn
+    let transform = match &widget.style.transform {
+        Some(widget_transform) => transform * widget_affine(widget_transform, node),
+        None => transform,
+    };
+    let opacity = opacity * widget.style.opacity.unwrap_or(1.0);
+    if opacity <= 0.0 {
+        return;
+    }
+    let direction = widget.style.direction.unwrap_or(direction);
+
+    let mut bounds = Rect::new(
+        node.x as f64,
+        node.y as f64,
+        (node.x + node.width) as f64,
+        (node.y + node.height) as f64,
+    );
+    let mut hasher = DefaultHasher::new();
+    transform.as_coeffs().map(f64::to_bits).hash(&mut hasher);
+    opacity.to_bits().hash(&mut hasher);
+
+    // Text can be wider than its node
+    if let Some(text) = layout_engine.text_layout(widget.id) {
+        // Lines move to the other side when the direction changes
+        direction.hash(&mut hasher);
+        for line in text.lines() {
+            line.metrics().offset.to_bits().hash(&mut hasher);
+        }
+
+        let scale = layout_engine.scale() as f64;
+        bounds = bounds.union(Rect::new(
+            node.x as f64,
+            node.y as f64,
+            node.x as f64 + text.full_width() as f64 / scale,
+            node.y as f64 + text.height() as f64 / scale,
+        ));
+    }
+
+    match &widget.element {
+        WidgetElement::Label {
+            content,
+            font_size,
+            weight,
+            style,
+            color,
+        } => {
+            content.hash(&mut hasher);
+            font_size.hash(&mut hasher);
+            weight.hash(&mut hasher);
+            style.hash(&mut hasher);
+            color.hash(&mut hasher);
+        }
+        WidgetElement::RichText {
+            spans,
+            link_hover_color,
+            ..
+        } => {
+            spans.hash(&mut hasher);
+            link_hover_color.hash(&mut hasher);
+            // Hovered link is drawn with another color
+            let hovered_span = match hovered_link {
+                Some((id, span)) if id == widget.id => Some(span),
+                _ => None,
+            };
+            hovered_span.hash(&mut hasher);
+        }
+        WidgetElement::Image {
+            image,
+            animation,
+            fit,
+            crop,
+            opacity,
+            tint,
+        } => {
+            // Animated images change with the shown frame
+            let image = match animation {
+                Some(animation) => animation.frame_at(time),
+                None => image,
+            };
+            image.image.data.id().hash(&mut hasher);
+            fit.hash(&mut hasher);
+            crop.hash(&mut hasher);
+            opacity.to_bits().hash(&mut hasher);
+            tint.hash(&mut hasher);
+        }
+        WidgetElement::Container {
+            child,
+            background,
+            radius,
+            border,
+            shadows,
+            clip,
+        } => {
+            background.hash(&mut hasher);
+            radius.hash(&mut hasher);
+            border.hash(&mut hasher);
+            shadows.hash(&mut hasher);
+            clip.hash(&mut hasher);
+            bounds = drop_shadows_bounds(shadows, bounds);
+            collect(
+                layout_engine,
+                hovered_link,
+                child,
+                transform,
+                opacity,
+                direction,
+                time,
+                widgets,
+            );
+        }
+        WidgetElement::VStack { children }
+        | WidgetElement::HStack { children }
+        | WidgetElement::Wrap { children, .. } => {
+            for child in children {
+                collect(
+                    layout_engine,
+                    hovered_link,
+                    child,
+                    transform,
+                    opacity,
+                    direction,
+                    time,
+                    widgets,
+                );
+            }
+            // Stacks don't paint anything themselves
+            return;
+        }
+        WidgetElement::Spacer {} => return,
+    }
+
+    // Antialiased edges reach a bit outside
+    let bounds = transform.transform_rect_bbox(bounds).inflate(1.0, 1.0);
+    widgets.insert(widget.id, (bounds, hasher.finish()));
+}
@@ -1,120 +1,269 @@
+use std::time::Instant;
+
 use glazeui_core::{Widget, WidgetElement};
 use glazeui_layout::LayoutEngine;
+use kurbo::{Affine, Rect, Vec2};
 use multirender::PaintScene;
-use parley::{FontContext, LayoutContext};
-use peniko::color::AlphaColor;
+use peniko::Mix;
 
-use crate::widgets::{draw_image::draw_image, draw_rect::draw_rectangle, draw_text::draw_text};
+use crate::{
+    transform::widget_affine,
+    widgets::{
+        draw_border::draw_border,
+        draw_image::draw_image,
+        draw_rect::{draw_rectangle, push_clip, radii},
+        draw_rich_text::draw_rich_text,
+        draw_shadow::{draw_drop_shadows, draw_inset_shadows, drop_shadows_bounds},
+        draw_text::draw_text,
+    },
+};
 
+/// Draw the widget and its children, `transform` is applied to everything drawn
+///
+/// Animated images show the frame at `time`, the same for the whole scene
 pub fn draw<M: Clone + Send + 'static, T: PaintScene>(
     scene: &mut T,
-    font_context: &mut FontContext,
-    registred_fallback_font: bool,
-    layout_context: &mut LayoutContext,
-    layout_engine: &mut LayoutEngine<M>,
-    scale: f32,
+    layout_engine: &LayoutEngine<M>,
+    hovered_link: Option<(u64, usize)>,
     widget: &Widget<M>,
+    transform: Affine,
+    time: Instant,
 ) {
     let widget_layout = layout_engine.get(widget.id).unwrap();
 
+    // Widget transform applies to its children too
+    let transform = match &widget.style.transform {
+        Some(widget_transform) => transform * widget_affine(widget_transform, widget_layout),
+        None => transform,
+    };
+
+    // Subtree with opacity is drawn in its own layer and faded as a whole
+    let opacity = widget.style.opacity.unwrap_or(1.0);
+    if opacity <= 0.0 {
+        return;
+    }
+    let layered = opacity < 1.0;
+    if layered {
+        let bounds = paint_bounds(layout_engine, widget);
+        scene.push_layer(Mix::Normal, opacity, transform, &bounds);
+    }
+
+    // Text was shaped by the layout engine in device pixels
+    let text = layout_engine.text_layout(widget.id);
+    let text_transform = transform
+        * Affine::translate(Vec2::new(widget_layout.x as f64, widget_layout.y as f64))
+        * Affine::scale(1.0 / layout_engine.scale() as f64);
+
     // Check if widget is label
-    if let WidgetElement::Label {
-        content,
-        font_size,
-        weight,
-        color,
-        style,
-    } = &widget.element
+    if let (WidgetElement::Label { .. }, Some(text)) = (&widget.element, text) {
+        draw_text(scene, text_transform, text);
+    }
+
+    // Check if widget is rich text
+    if let (
+        WidgetElement::RichText {
+            link_hover_color, ..
+        },
+        Some(text),
+    ) = (&widget.element, text)
     {
-        let color = AlphaColor::from_rgba8(color.0, color.1, color.2, color.3);
+        let hovered_span = match hovered_link {
+            Some((id, span)) if id == widget.id => Some(span),
+            _ => None,
+        };
 
-        draw_text(
-            scene,
-            widget_layout.x as f64,
-            widget_layout.y as f64,
-            font_context,
-            registred_fallback_font,
-            content,
-            color,
-            weight,
-            style,
-            widget.style.spacing,
-            *font_size as f32,
-            scale,
-            layout_context,
-        );
+        draw_rich_text(scene, text_transform, text, hovered_span, *link_hover_color);
     }
 
     // Check if widget is image
-    if let WidgetElement::Image { image, .. } = &widget.element {
+    if let WidgetElement::Image {
+        image,
+        animation,
+        fit,
+        crop,
+        opacity,
+        tint,
+    } = &widget.element
+    {
+        // Animated images show the frame for the current time
+        let image = match animation {
+            Some(animation) => animation.frame_at(time),
+            None => image,
+        };
+
         draw_image(
             scene,
+            transform,
             image.as_ref(),
             widget_layout.x as f64,
             widget_layout.y as f64,
+            widget_layout.width as f64,
+            widget_layout.height as f64,
+            *fit,
+            *crop,
+            *opacity,
+            *tint,
         );
     }
 
     // Check if widget is container
     if let WidgetElement::Container {
         child,
-        color,
+        background,
         radius,
+        border,
+        shadows,
+        clip,
     } = &widget.element
     {
-        let width = widget.style.width as f64;
-        let height = widget.style.height as f64;
+        let width = widget_layout.width as f64;
+        let height = widget_layout.height as f64;
+
+        draw_drop_shadows(
+            scene,
+            transform,
+            shadows,
+            radius,
+            widget_layout.x as f64,
+            widget_layout.y as f64,
+            width,
+            height,
+        );
 
         // Draw container (rectangle)
         draw_rectangle(
             scene,
-            *radius as f64,
-            color,
+            transform,
+            radii(radius),
+            background,
             widget_layout.x as f64,
             widget_layout.y as f64,
             width,
             height,
         );
 
-        // Draw container child
-        draw(
+        draw_inset_shadows(
             scene,
-            font_context,
-            registred_fallback_font,
-            layout_context,
-            layout_engine,
-            scale,
-            child,
+            transform,
+            shadows,
+            radius,
+            widget_layout.x as f64,
+            widget_layout.y as f64,
+            width,
+            height,
         );
+
+        // Draw container child, masked to the container when it clips
+        if *clip {
+            push_clip(
+                scene,
+                transform,
+                radii(radius),
+                widget_layout.x as f64,
+                widget_layout.y as f64,
+                width,
+                height,
+            );
+        }
+        draw(scene, layout_engine, hovered_link, child, transform, time);
+        if *clip {
+            scene.pop_layer();
+        }
+
+        // Border is drawn over the child, so clipped content doesn't cover it
+        if let Some(border) = border {
+            draw_border(
+                scene,
+                transform,
+                border,
+                radius,
+                widget_layout.x as f64,
+                widget_layout.y as f64,
+                width,
+                height,
+            );
+        }
     }
 
     // Check if widget is vstack
     if let WidgetElement::VStack { children, .. } = &widget.element {
         for child in children.iter() {
-            draw(
-                scene,
-                font_context,
-                registred_fallback_font,
-                layout_context,
-                layout_engine,
-                scale,
-                child,
-            );
+            draw(scene, layout_engine, hovered_link, child, transform, time);
         }
     }
 
     // Check if widget is hstack
     if let WidgetElement::HStack { children, .. } = &widget.element {
         for child in children.iter() {
-            draw(
-                scene,
-                font_context,
-                registred_fallback_font,
-                layout_context,
-                layout_engine,
-                scale,
-                child,
-            );
+            draw(scene, layout_engine, hovered_link, child, transform, time);
         }
     }
+
+    // Check if widget is wrap
+    if let WidgetElement::Wrap { children, .. } = &widget.element {
+        for child in children.iter() {
+            draw(scene, layout_engine, hovered_link, child, transform, time);
+        }
+    }
+
+    if layered {
+        scene.pop_layer();
+    }
+}
+
+/// Area the widget and its children can paint on, including drop shadows
+fn paint_bounds<M: Clone + Send + 'static>(
+    layout_engine: &LayoutEngine<M>,
+    widget: &Widget<M>,
+) -> Rect {
+    let node_bounds = match layout_engine.get(widget.id) {
+        Some(node) => Rect::new(
+            node.x as f64,
+            node.y as f64,
+            (node.x + node.width) as f64,
+            (node.y + node.height) as f64,
+        ),
+        None => Rect::ZERO,
+    };
+    let mut bounds = node_bounds;
+
+    // Text can be wider than its node
+    if let Some(text) = layout_engine.text_layout(widget.id) {
+        let scale = layout_engine.scale() as f64;
+        bounds = bounds.union(Rect::new(
+            node_bounds.x0,
+            node_bounds.y0,
+            node_bounds.x0 + text.full_width() as f64 / scale,
+            node_bounds.y0 + text.height() as f64 / scale,
+        ));
+    }
+
+    match &widget.element {
+        WidgetElement::Container { child, shadows, .. } => {
+            bounds = drop_shadows_bounds(shadows, node_bounds);
+            bounds = bounds.union(child_paint_bounds(layout_engine, child));
+        }
+        WidgetElement::VStack { children }
+        | WidgetElement::HStack { children }
+        | WidgetElement::Wrap { children, .. } => {
+            for child in children {
+                bounds = bounds.union(child_paint_bounds(layout_engine, child));
+            }
+        }
+        _ => {}
+    }
+
+    bounds
+}
+
+/// Paint bounds of the child in the space of its parent, moved by the child transform
+fn child_paint_bounds<M: Clone + Send + 'static>(
+    layout_engine: &LayoutEngine<M>,
+    child: &Widget<M>,
+) -> Rect {
+    let bounds = paint_bounds(layout_engine, child);
+    match (&child.style.transform, layout_engine.get(child.id)) {
+        (Some(transform), Some(node)) => widget_affine(transform, node).transform_rect_bbox(bounds),
+        _ => bounds,
+    }
 }
@@ -11,7 +11,10 @@ use multirender_vello_cpu::VelloCpuImageRenderer;
 use multirender_vello_hybrid::VelloHybridWindowRenderer;
 use winit::window::Window;
 
+pub mod damage;
 pub mod draw;
+pub mod svg;
+pub mod transform;
 pub mod widgets;
 
 pub enum Renderer {
@@ -0,0 +1,850 @@
+use std::fmt::Write;
+use std::io::Cursor;
+
+use kurbo::{Affine, BezPath, Cap, Join, Rect, Shape, Stroke};
+use multirender::{Glyph, NormalizedCoord, PaintScene};
+use peniko::{
+    BlendMode, Brush, BrushRef, Color, Compose, Extend, FontData, Gradient, GradientKind,
+    ImageAlphaType, ImageBrush, ImageData, ImageFormat, Mix, Style, StyleRef,
+    color::{DynamicColor, Srgb},
+};
+use skrifa::{
+    FontRef, GlyphId, MetadataProvider,
+    instance::{LocationRef, Size},
+    outline::{DrawSettings, OutlinePen},
+    raw::types::F2Dot14,
+};
+
+/// Tolerance for turning shapes into paths
+const TOLERANCE: f64 = 0.1;
+
+/// Paint scene that records what is drawn, to save it as SVG
+///
+/// Text is saved as outlines, so the SVG looks the same without the fonts installed.
+/// SVG has no sweep gradients, they are drawn with the color of their first stop.
+#[derive(Default)]
+pub struct SvgScene {
+    commands: Vec<Command>,
+}
+
+enum Command {
+    PushLayer {
+        blend: BlendMode,
+        alpha: f32,
+        transform: Affine,
+        clip: BezPath,
+    },
+    PopLayer,
+    Draw {
+        style: Box<Style>,
+        transform: Affine,
+        brush: Box<Brush>,
+        brush_transform: Option<Affine>,
+        path: BezPath,
+    },
+    BoxShadow {
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    },
+    Image {
+        image: ImageBrush,
+        transform: Affine,
+    },
+}
+
+impl SvgScene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// SVG document of everything drawn since the last reset, `width` and `height` are its size
+    pub fn to_svg(&self, width: f64, height: f64) -> String {
+        let mut writer = SvgWriter::default();
+        writer.commands(&self.commands);
+
+        let mut svg = String::new();
+        write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            number(width),
+            number(height),
+            number(width),
+            number(height)
+        )
+        .ok();
+        if writer.uses_black {
+            // Turns colors black and keeps alpha, for masks that cut shapes out
+            svg.push_str(r#"<filter id="black"><feColorMatrix type="matrix" values="0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0"/></filter>"#);
+        }
+        if writer.uses_white {
+            // Turns colors white and keeps alpha, for masks of what is already drawn
+            svg.push_str(r#"<filter id="white"><feColorMatrix type="matrix" values="0 0 0 0 1 0 0 0 0 1 0 0 0 0 1 0 0 0 1 0"/></filter>"#);
+        }
+        svg.push_str(&writer.svg);
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+impl PaintScene for SvgScene {
+    fn reset(&mut self) {
+        self.commands.clear();
+    }
+
+    fn push_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        alpha: f32,
+        transform: Affine,
+        clip: &impl Shape,
+    ) {
+        self.commands.push(Command::PushLayer {
+            blend: blend.into(),
+            alpha,
+            transform,
+            clip: clip.to_path(TOLERANCE),
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        self.commands.push(Command::PopLayer);
+    }
+
+    fn stroke<'a>(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        brush: impl Into<BrushRef<'a>>,
+        brush_transform: Option<Affine>,
+        shape: &impl Shape,
+    ) {
+        self.commands.push(Command::Draw {
+            style: Box::new(Style::Stroke(style.clone())),
+            transform,
+            brush: Box::new(brush.into().to_owned()),
+            brush_transform,
+            path: shape.to_path(TOLERANCE),
+        });
+    }
+
+    fn fill<'a>(
+        &mut self,
+        style: peniko::Fill,
+        transform: Affine,
+        brush: impl Into<BrushRef<'a>>,
+        brush_transform: Option<Affine>,
+        shape: &impl Shape,
+    ) {
+        self.commands.push(Command::Draw {
+            style: Box::new(Style::Fill(style)),
+            transform,
+            brush: Box::new(brush.into().to_owned()),
+            brush_transform,
+            path: shape.to_path(TOLERANCE),
+        });
+    }
+
+    fn draw_glyphs<'a, 's: 'a>(
+        &'s mut self,
+        font: &'a FontData,
+        font_size: f32,
+        _hint: bool,
+        normalized_coords: &'a [NormalizedCoord],
+        style: impl Into<StyleRef<'a>>,
+        brush: impl Into<BrushRef<'a>>,
+        brush_alpha: f32,
+        transform: Affine,
+        glyph_transform: Option<Affine>,
+        glyphs: impl Iterator<Item = Glyph>,
+    ) {
+        let Ok(font_ref) = FontRef::from_index(font.data.data(), font.index) else {
+            return;
+        };
+        let outlines = font_ref.outline_glyphs();
+        let coords: Vec<F2Dot14> = normalized_coords
+            .iter()
+            .map(|coord| F2Dot14::from_bits(*coord))
+            .collect();
+        let glyph_transform = glyph_transform.unwrap_or(Affine::IDENTITY);
+
+        // Outlines of all glyphs as one path, font units point up
+        let mut path = BezPath::new();
+        for glyph in glyphs {
+            let Some(outline) = outlines.get(GlyphId::new(glyph.id)) else {
+                continue;
+            };
+            let mut pen = PathPen(BezPath::new());
+            let settings = DrawSettings::unhinted(Size::new(font_size), LocationRef::new(&coords));
+            if outline.draw(settings, &mut pen).is_err() {
+                continue;
+            }
+            let placement = Affine::translate((glyph.x as f64, glyph.y as f64))
+                * glyph_transform
+                * Affine::FLIP_Y;
+            path.extend(placement * pen.0);
+        }
+
+        self.commands.push(Command::Draw {
+            style: Box::new(style.into().to_owned()),
+            transform,
+            brush: Box::new(brush.into().to_owned().multiply_alpha(brush_alpha)),
+            brush_transform: None,
+            path,
+        });
+    }
+
+    fn draw_box_shadow(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        brush: Color,
+        radius: f64,
+        std_dev: f64,
+    ) {
+        self.commands.push(Command::BoxShadow {
+            transform,
+            rect,
+            color: brush,
+            radius,
+            std_dev,
+        });
+    }
+
+    fn draw_image(&mut self, image: ImageBrush<&ImageData>, transform: Affine) {
+        self.commands.push(Command::Image {
+            image: ImageBrush {
+                image: image.image.clone(),
+                sampler: image.sampler,
+            },
+            transform,
+        });
+    }
+}
+
+/// Builds a kurbo path from glyph outlines
+struct PathPen(BezPath);
+
+impl OutlinePen for PathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((x as f64, y as f64));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((x as f64, y as f64));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.0
+            .quad_to((cx0 as f64, cy0 as f64), (x as f64, y as f64));
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.0.curve_to(
+            (cx0 as f64, cy0 as f64),
+            (cx1 as f64, cy1 as f64),
+            (x as f64, y as f64),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
+}
+
+#[derive(Default)]
+struct SvgWriter {
+    svg: String,
+    /// Counter for ids of clip paths, gradients, masks and filters
+    next_id: usize,
+    uses_black: bool,
+    uses_white: bool,
+    /// Where the content of every open layer starts in `svg`
+    layer_starts: Vec<usize>,
+}
+
+impl SvgWriter {
+    fn id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Write the commands until the end of the current layer, returns the commands left after it
+    fn commands<'a>(&mut self, mut commands: &'a [Command]) -> &'a [Command] {
+        while let Some((command, rest)) = commands.split_first() {
+            commands = rest;
+            match command {
+                Command::PushLayer {
+                    blend,
+                    alpha,
+                    transform,
+                    clip,
+                } => commands = self.layer(blend, *alpha, *transform, clip, commands),
+                Command::PopLayer => return commands,
+                Command::Draw {
+                    style,
+                    transform,
+                    brush,
+                    brush_transform,
+                    path,
+                } => self.draw(style, *transform, brush, *brush_transform, path),
+                Command::BoxShadow {
+                    transform,
+                    rect,
+                    color,
+                    radius,
+                    std_dev,
+                } => self.box_shadow(*transform, *rect, *color, *radius, *std_dev),
+                Command::Image { image, transform } => self.image(image, *transform),
+            }
+        }
+        commands
+    }
+
+    fn layer<'a>(
+        &mut self,
+        blend: &BlendMode,
+        alpha: f32,
+        transform: Affine,
+        clip: &BezPath,
+        commands: &'a [Command],
+    ) -> &'a [Command] {
+        let clip_id = self.id();
+        write!(
+            self.svg,
+            r#"<clipPath id="clip{clip_id}"><path transform="{}" d="{}"/></clipPath>"#,
+            matrix(transform),
+            clip.to_svg()
+        )
+        .ok();
+
+        // Layer that cuts out of what is under it, becomes a mask of what its parent drew so far
+        if blend.compose == Compose::DestOut {
+            let bounds = transform.transform_rect_bbox(clip.bounding_box());
+            let mask_id = self.id();
+            self.uses_black = true;
+
+            let parent_start = self.layer_starts.last().copied().unwrap_or(0);
+            let masked = self.svg.split_off(parent_start);
+            write!(
+                self.svg,
+                r#"<mask id="mask{mask_id}" maskUnits="userSpaceOnUse" {bounds}><rect {bounds} fill="white"/><g clip-path="url(#clip{clip_id})" filter="url(#black)"{}>"#,
+                opacity(alpha),
+                bounds = rect_attributes(bounds)
+            )
+            .ok();
+            self.layer_starts.push(self.svg.len());
+            let commands = self.commands(commands);
+            self.layer_starts.pop();
+            write!(
+                self.svg,
+                r#"</g></mask><g mask="url(#mask{mask_id})">{masked}</g>"#
+            )
+            .ok();
+            return commands;
+        }
+
+        // Blend modes only mix with the parent layer, not with everything under it
+        let mix = mix_blend_mode(blend.mix);
+        let blended = mix.is_some() || blend.compose == Compose::SrcAtop;
+        let mut mask = None;
+        if blended {
+            let parent_start = self.layer_starts.last().copied().unwrap_or(0);
+            let parent = self.svg.split_off(parent_start);
+            self.svg.push_str(r#"<g style="isolation:isolate">"#);
+
+            // Layer only draws on top of what its parent drew so far, like an image tint
+            if blend.compose == Compose::SrcAtop {
+                let bounds = transform.transform_rect_bbox(clip.bounding_box());
+                let content_id = self.id();
+                let mask_id = self.id();
+                self.uses_white = true;
+                write!(
+                    self.svg,
+                    r##"<g id="content{content_id}">{parent}</g><mask id="mask{mask_id}" maskUnits="userSpaceOnUse" {}><use xlink:href="#content{content_id}" filter="url(#white)"/></mask>"##,
+                    rect_attributes(bounds)
+                )
+                .ok();
+                mask = Some(mask_id);
+            } else {
+                self.svg.push_str(&parent);
+            }
+        }
+
+        write!(
+            self.svg,
+            r#"<g clip-path="url(#clip{clip_id})"{}"#,
+            opacity(alpha)
+        )
+        .ok();
+        if let Some(mask_id) = mask {
+            write!(self.svg, r#" mask="url(#mask{mask_id})""#).ok();
+        }
+        if let Some(mix) = mix {
+            write!(self.svg, r#" style="mix-blend-mode:{mix}""#).ok();
+        }
+        self.svg.push('>');
+        self.layer_starts.push(self.svg.len());
+        let commands = self.commands(commands);
+        self.layer_starts.pop();
+        self.svg.push_str("</g>");
+        if blended {
+            self.svg.push_str("</g>");
+        }
+        commands
+    }
+
+    fn draw(
+        &mut self,
+        style: &Style,
+        transform: Affine,
+        brush: &Brush,
+        brush_transform: Option<Affine>,
+        path: &BezPath,
+    ) {
+        let paint = self.paint(brush, brush_transform);
+        write!(
+            self.svg,
+            r#"<path transform="{}" d="{}""#,
+            matrix(transform),
+            path.to_svg()
+        )
+        .ok();
+
+        match style {
+            Style::Fill(fill) => {
+                let rule = match fill {
+                    peniko::Fill::NonZero => "nonzero",
+                    peniko::Fill::EvenOdd => "evenodd",
+                };
+                write!(self.svg, r#" fill="{}" fill-rule="{rule}""#, paint.0).ok();
+                if let Some(alpha) = paint.1 {
+                    write!(self.svg, r#" fill-opacity="{}""#, number(alpha)).ok();
+                }
+            }
+            Style::Stroke(stroke) => {
+                write!(
+                    self.svg,
+                    r#" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}""#,
+                    paint.0,
+                    number(stroke.width),
+                    line_cap(stroke.start_cap),
+                    line_join(stroke.join),
+                    number(stroke.miter_limit)
+                )
+                .ok();
+                if let Some(alpha) = paint.1 {
+                    write!(self.svg, r#" stroke-opacity="{}""#, number(alpha)).ok();
+                }
+                if !stroke.dash_pattern.is_empty() {
+                    let dashes: Vec<String> = stroke
+                        .dash_pattern
+                        .iter()
+                        .map(|dash| number(*dash))
+                        .collect();
+                    write!(
+                        self.svg,
+                        r#" stroke-dasharray="{}" stroke-dashoffset="{}""#,
+                        dashes.join(" "),
+                        number(stroke.dash_offset)
+                    )
+                    .ok();
+                }
+            }
+        }
+        self.svg.push_str("/>");
+    }
+
+    /// Value of a fill or stroke attribute with its opacity, gradients are written as definitions
+    fn paint(&mut self, brush: &Brush, brush_transform: Option<Affine>) -> (String, Option<f64>) {
+        match brush {
+            Brush::Solid(color) => (rgb(*color), Some(alpha(*color))),
+            Brush::Gradient(gradient) => match self.gradient(gradient, brush_transform) {
+                Some(id) => (format!("url(#gradient{id})"), None),
+                None => {
+                    // Sweep gradients fall back to their first color
+                    let color = gradient
+                        .stops
+                        .first()
+                        .map(|stop| stop_color(&stop.color))
+                        .unwrap_or(Color::TRANSPARENT);
+                    (rgb(color), Some(alpha(color)))
+                }
+            },
+            // Images are drawn with `draw_image`, other image brushes aren't supported
+            Brush::Image(_) => ("none".to_string(), None),
+        }
+    }
+
+    fn gradient(&mut self, gradient: &Gradient, brush_transform: Option<Affine>) -> Option<usize> {
+        let id = self.id();
+        let spread = match gradient.extend {
+            Extend::Pad => "pad",
+            Extend::Repeat => "repeat",
+            Extend::Reflect => "reflect",
+        };
+        let transform = match brush_transform {
+            Some(transform) => format!(r#" gradientTransform="{}""#, matrix(transform)),
+            None => String::new(),
+        };
+
+        match &gradient.kind {
+            GradientKind::Linear(position) => write!(
+                self.svg,
+                r#"<linearGradient id="gradient{id}" gradientUnits="userSpaceOnUse" spreadMethod="{spread}"{transform} x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                number(position.start.x),
+                number(position.start.y),
+                number(position.end.x),
+                number(position.end.y)
+            )
+            .ok(),
+            GradientKind::Radial(position) => write!(
+                self.svg,
+                r#"<radialGradient id="gradient{id}" gradientUnits="userSpaceOnUse" spreadMethod="{spread}"{transform} fx="{}" fy="{}" fr="{}" cx="{}" cy="{}" r="{}">"#,
+                number(position.start_center.x),
+                number(position.start_center.y),
+                number(position.start_radius as f64),
+                number(position.end_center.x),
+                number(position.end_center.y),
+                number(position.end_radius as f64)
+            )
+            .ok(),
+            GradientKind::Sweep(_) => return None,
+        };
+
+        for stop in gradient.stops.iter() {
+            let color = stop_color(&stop.color);
+            write!(
+                self.svg,
+                r#"<stop offset="{}" stop-color="{}" stop-opacity="{}"/>"#,
+                number(stop.offset as f64),
+                rgb(color),
+                number(alpha(color))
+            )
+            .ok();
+        }
+
+        match gradient.kind {
+            GradientKind::Linear(_) => self.svg.push_str("</linearGradient>"),
+            _ => self.svg.push_str("</radialGradient>"),
+        }
+        Some(id)
+    }
+
+    fn box_shadow(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    ) {
+        let id = self.id();
+        // Blur reaches three standard deviations
+        let region = rect.inflate(std_dev * 3.0, std_dev * 3.0);
+        write!(
+            self.svg,
+            r#"<filter id="shadow{id}" filterUnits="userSpaceOnUse" {}><feGaussianBlur stdDeviation="{}"/></filter>"#,
+            rect_attributes(region),
+            number(std_dev)
+        )
+        .ok();
+        write!(
+            self.svg,
+            r#"<rect transform="{}" {} rx="{}" fill="{}" fill-opacity="{}" filter="url(#shadow{id})"/>"#,
+            matrix(transform),
+            rect_attributes(rect),
+            number(radius),
+            rgb(color),
+            number(alpha(color))
+        )
+        .ok();
+    }
+
+    fn image(&mut self, image: &ImageBrush, transform: Affine) {
+        let Some(png) = encode_png(&image.image) else {
+            return;
+        };
+        write!(
+            self.svg,
+            r#"<image transform="{}" width="{}" height="{}" preserveAspectRatio="none"{} xlink:href="data:image/png;base64,{}"/>"#,
+            matrix(transform),
+            image.image.width,
+            image.image.height,
+            opacity(image.sampler.alpha),
+            base64(&png)
+        )
+        .ok();
+    }
+}
+
+/// PNG file of the image pixels, with straight alpha like PNG expects
+fn encode_png(image: &ImageData) -> Option<Vec<u8>> {
+    let mut pixels = image.data.data().to_vec();
+    for pixel in pixels.chunks_exact_mut(4) {
+        if image.format == ImageFormat::Bgra8 {
+            pixel.swap(0, 2);
+        }
+        let alpha = pixel[3] as u32;
+        if image.alpha_type == ImageAlphaType::AlphaPremultiplied && alpha > 0 {
+            for channel in &mut pixel[..3] {
+                *channel = (*channel as u32 * 255 / alpha).min(255) as u8;
+            }
+        }
+    }
+
+    let buffer = image::RgbaImage::from_raw(image.width, image.height, pixels)?;
+    let mut png = Vec::new();
+    buffer
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .ok()?;
+    Some(png)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - index * 6) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn mix_blend_mode(mix: Mix) -> Option<&'static str> {
+    let mode = match mix {
+        Mix::Normal => return None,
+        Mix::Multiply => "multiply",
+        Mix::Screen => "screen",
+        Mix::Overlay => "overlay",
+        Mix::Darken => "darken",
+        Mix::Lighten => "lighten",
+        Mix::ColorDodge => "color-dodge",
+        Mix::ColorBurn => "color-burn",
+        Mix::HardLight => "hard-light",
+        Mix::SoftLight => "soft-light",
+        Mix::Difference => "difference",
+        Mix::Exclusion => "exclusion",
+        Mix::Hue => "hue",
+        Mix::Saturation => "saturation",
+        Mix::Color => "color",
+        Mix::Luminosity => "luminosity",
+    };
+    Some(mode)
+}
+
+fn line_cap(cap: Cap) -> &'static str {
+    match cap {
+        Cap::Butt => "butt",
+        Cap::Square => "square",
+        Cap::Round => "round",
+    }
+}
+
+fn line_join(join: Join) -> &'static str {
+    match join {
+        Join::Bevel => "bevel",
+        Join::Miter => "miter",
+        Join::Round => "round",
+    }
+}
+
+fn stop_color(color: &DynamicColor) -> Color {
+    color.to_alpha_color::<Srgb>()
+}
+
+fn rgb(color: Color) -> String {
+    let color = color.to_rgba8();
+    format!("rgb({},{},{})", color.r, color.g, color.b)
+}
+
+fn alpha(color: Color) -> f64 {
+    color.components[3] as f64
+}
+
+/// Opacity attribute, empty when fully opaque
+fn opacity(alpha: f32) -> String {
+    if alpha < 1.0 {
+        format!(r#" opacity="{}""#, number(alpha as f64))
+    } else {
+        String::new()
+    }
+}
+
+fn matrix(transform: Affine) -> String {
+    let [a, b, c, d, e, f] = transform.as_coeffs().map(number);
+    format!("matrix({a} {b} {c} {d} {e} {f})")
+}
+
+fn rect_attributes(rect: Rect) -> String {
+    format!(
+        r#"x="{}" y="{}" width="{}" height="{}""#,
+        number(rect.x0),
+        number(rect.y0),
+        number(rect.width()),
+        number(rect.height())
+    )
+}
+
+/// Number rounded to thousandths, SVG doesn't need more
+fn number(value: f64) -> String {
+    let value = (value * 1000.0).round() / 1000.0;
+    if value == 0.0 {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+    use peniko::Fill;
+
+    fn rect() -> Rect {
+        Rect::new(0.0, 0.0, 10.0, 20.0)
+    }
+
+    fn fill(scene: &mut SvgScene, color: Color) {
+        scene.fill(Fill::NonZero, Affine::IDENTITY, color, None, &rect());
+    }
+
+    #[test]
+    fn base64_vectors() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0xfd]), "//79");
+    }
+
+    #[test]
+    fn numbers_are_rounded() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(2.5), "2.5");
+        assert_eq!(number(0.12345), "0.123");
+        assert_eq!(number(-0.0001), "0");
+        assert_eq!(number(-12.0), "-12");
+    }
+
+    #[test]
+    fn matrix_coefficients() {
+        assert_eq!(matrix(Affine::IDENTITY), "matrix(1 0 0 1 0 0)");
+        assert_eq!(
+            matrix(Affine::translate((10.0, 20.5))),
+            "matrix(1 0 0 1 10 20.5)"
+        );
+        assert_eq!(matrix(Affine::scale(2.0)), "matrix(2 0 0 2 0 0)");
+    }
+
+    #[test]
+    fn solid_fill() {
+        let mut scene = SvgScene::new();
+        fill(&mut scene, Color::from_rgba8(255, 0, 0, 255));
+        let svg = scene.to_svg(100.0, 50.0);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+        assert!(svg.contains(r#"width="100" height="50" viewBox="0 0 100 50""#));
+        assert!(svg.contains(
+            r#"<path transform="matrix(1 0 0 1 0 0)" d="M0,0 L10,0 L10,20 L0,20 Z" fill="rgb(255,0,0)" fill-rule="nonzero" fill-opacity="1"/>"#
+        ));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn dashed_stroke() {
+        let mut scene = SvgScene::new();
+        let stroke = Stroke::new(2.0).with_dashes(1.0, [4.0, 2.0]);
+        scene.stroke(
+            &stroke,
+            Affine::IDENTITY,
+            Color::from_rgba8(0, 0, 255, 128),
+            None,
+            &rect(),
+        );
+        let svg = scene.to_svg(100.0, 50.0);
+
+        assert!(svg.contains(r#"fill="none" stroke="rgb(0,0,255)" stroke-width="2""#));
+        assert!(svg.contains(r#"stroke-opacity="0.502""#));
+        assert!(svg.contains(r#"stroke-dasharray="4 2" stroke-dashoffset="1""#));
+    }
+
+    #[test]
+    fn linear_gradient() {
+        let mut scene = SvgScene::new();
+        let gradient = Gradient::new_linear(Point::new(0.0, 0.0), Point::new(10.0, 0.0))
+            .with_stops([Color::BLACK, Color::WHITE]);
+        scene.fill(Fill::NonZero, Affine::IDENTITY, &gradient, None, &rect());
+        let svg = scene.to_svg(100.0, 50.0);
+
+        assert!(svg.contains(
+            r#"<linearGradient id="gradient1" gradientUnits="userSpaceOnUse" spreadMethod="pad" x1="0" y1="0" x2="10" y2="0">"#
+        ));
+        assert!(svg.contains(r#"<stop offset="0" stop-color="rgb(0,0,0)" stop-opacity="1"/>"#));
+        assert!(
+            svg.contains(r#"<stop offset="1" stop-color="rgb(255,255,255)" stop-opacity="1"/>"#)
+        );
+        assert!(svg.contains(r#"fill="url(#gradient1)""#));
+    }
+
+    #[test]
+    fn dest_out_layer_masks_parent() {
+        let mut scene = SvgScene::new();
+        fill(&mut scene, Color::from_rgba8(255, 0, 0, 255));
+        scene.push_layer(
+            BlendMode::new(Mix::Normal, Compose::DestOut),
+            1.0,
+            Affine::IDENTITY,
+            &rect(),
+        );
+        fill(&mut scene, Color::BLACK);
+        scene.pop_layer();
+        let svg = scene.to_svg(100.0, 50.0);
+
+        assert!(svg.contains(r#"<filter id="black">"#));
+        assert!(svg.contains(r#"<mask id="mask2" maskUnits="userSpaceOnUse" x="0" y="0" width="10" height="20"><rect x="0" y="0" width="10" height="20" fill="white"/><g clip-path="url(#clip1)" filter="url(#black)">"#));
+        // Red fill is drawn through the mask, after it
+        let mask_end = svg.find("</mask>").unwrap();
+        let red = svg.find("rgb(255,0,0)").unwrap();
+        assert!(red > mask_end);
+        assert!(svg.contains(r#"<g mask="url(#mask2)">"#));
+    }
+
+    #[test]
+    fn src_atop_layer_draws_on_parent() {
+        let mut scene = SvgScene::new();
+        fill(&mut scene, Color::from_rgba8(255, 0, 0, 255));
+        scene.push_layer(
+            BlendMode::new(Mix::Multiply, Compose::SrcAtop),
+            1.0,
+            Affine::IDENTITY,
+            &rect(),
+        );
+        fill(&mut scene, Color::from_rgba8(0, 0, 255, 255));
+        scene.pop_layer();
+        let svg = scene.to_svg(100.0, 50.0);
+
+        assert!(svg.contains(r#"<filter id="white">"#));
+        assert!(svg.contains(r#"<g style="isolation:isolate"><g id="content2"><path"#));
+        assert!(svg.contains(r##"<use xlink:href="#content2" filter="url(#white)"/>"##));
+        assert!(svg.contains(
+            r#"<g clip-path="url(#clip1)" mask="url(#mask3)" style="mix-blend-mode:multiply">"#
+        ));
+        assert!(svg.ends_with("</g></g></svg>"));
+    }
+}
@@ -0,0 +1,17 @@
+use glazeui_core::Transform;
+use glazeui_layout::LayoutNode;
+use kurbo::{Affine, Vec2};
+
+/// Affine of the widget transform, rotation and scale are around the center of the node
+pub fn widget_affine(transform: &Transform, node: &LayoutNode) -> Affine {
+    let center = Vec2::new(
+        (node.x + node.width / 2.0) as f64,
+        (node.y + node.height / 2.0) as f64,
+    );
+    let translate = Vec2::new(transform.translate.0 as f64, transform.translate.1 as f64);
+
+    Affine::translate(center + translate)
+        * Affine::rotate((transform.rotate as f64).to_radians())
+        * Affine::scale_non_uniform(transform.scale.0 as f64, transform.scale.1 as f64)
+        * Affine::translate(-center)
+}
@@ -0,0 +1,115 @@
+use glazeui_core::{Border, Radius};
+use kurbo::{Affine, BezPath, Line, Rect, RoundedRect, RoundedRectRadii, Shape, Stroke};
+use multirender::PaintScene;
+use peniko::{Color, Fill};
+
+/// Draw the border inside the rectangle
+pub fn draw_border<T: PaintScene>(
+    scene: &mut T,
+    transform: Affine,
+    border: &Border,
+    radius: &Radius,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) {
+    let color = Color::from_rgba8(
+        border.color.r,
+        border.color.g,
+        border.color.b,
+        border.color.a,
+    );
+    let widths = &border.width;
+    let rect = Rect::new(x, y, x + width, y + height);
+
+    if widths.is_uniform() {
+        let line_width = widths.top as f64;
+        if line_width <= 0.0 {
+            return;
+        }
+
+        // Stroke is centered on the path, move it in by half of the width
+        let half = line_width / 2.0;
+        let shape = RoundedRect::from_rect(
+            rect.inset(-half),
+            inset_radii(radius, half, half, half, half),
+        );
+        let stroke = stroke(line_width, &border.dash);
+        scene.stroke(&stroke, transform, color, None, &shape);
+        return;
+    }
+
+    let (top, right, bottom, left) = (
+        widths.top as f64,
+        widths.right as f64,
+        widths.bottom as f64,
+        widths.left as f64,
+    );
+
+    if border.dash.is_empty() {
+        // Fill the ring between the outer and the inner rectangle
+        let outer = RoundedRect::from_rect(rect, inset_radii(radius, 0.0, 0.0, 0.0, 0.0));
+        let inner_rect = Rect::new(x + left, y + top, x + width - right, y + height - bottom);
+        let inner =
+            RoundedRect::from_rect(inner_rect, inset_radii(radius, top, right, bottom, left));
+
+        let mut ring = BezPath::new();
+        ring.extend(outer.path_elements(0.1));
+        ring.extend(inner.path_elements(0.1));
+        scene.fill(Fill::EvenOdd, transform, color, None, &ring);
+        return;
+    }
+
+    // Dashed sides with different widths are drawn one by one, without rounded corners
+    let sides = [
+        (
+            top,
+            Line::new((x, y + top / 2.0), (x + width, y + top / 2.0)),
+        ),
+        (
+            right,
+            Line::new(
+                (x + width - right / 2.0, y),
+                (x + width - right / 2.0, y + height),
+            ),
+        ),
+        (
+            bottom,
+            Line::new(
+                (x + width, y + height - bottom / 2.0),
+                (x, y + height - bottom / 2.0),
+            ),
+        ),
+        (
+            left,
+            Line::new((x + left / 2.0, y + height), (x + left / 2.0, y)),
+        ),
+    ];
+    for (line_width, line) in sides {
+        if line_width > 0.0 {
+            let stroke = stroke(line_width, &border.dash);
+            scene.stroke(&stroke, transform, color, None, &line);
+        }
+    }
+}
+
+fn stroke(width: f64, dash: &[f32]) -> Stroke {
+    let stroke = Stroke::new(width);
+    if dash.is_empty() {
+        stroke
+    } else {
+        stroke.with_dashes(0.0, dash.iter().map(|length| *length as f64))
+    }
+}
+
+/// Corners of a rectangle moved in by the side widths, they get smaller by the thicker side
+fn inset_radii(radius: &Radius, top: f64, right: f64, bottom: f64, left: f64) -> RoundedRectRadii {
+    let corner = |radius: f32, a: f64, b: f64| (radius as f64 - a.max(b)).max(0.0);
+    RoundedRectRadii::new(
+        corner(radius.top_left, top, left),
+        corner(radius.top_right, top, right),
+        corner(radius.bottom_right, bottom, right),
+        corner(radius.bottom_left, bottom, left),
+    )
+}
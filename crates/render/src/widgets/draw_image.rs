@@ -1,13 +1,111 @@
-use kurbo::{Affine, Vec2};
+use glazeui_core::{ContentFit, Rect as CropRect};
+use kurbo::{Affine, Rect, Vec2};
 use multirender::PaintScene;
-use peniko::{ImageBrush, ImageData};
+use peniko::{BlendMode, Color, Compose, Fill, ImageBrush, ImageData, Mix};
+
+/// Get scale of the image for x and y, so it fits the size
+fn fit_scale(
+    fit: ContentFit,
+    width: f64,
+    height: f64,
+    image_width: f64,
+    image_height: f64,
+) -> (f64, f64) {
+    let scale_x = width / image_width;
+    let scale_y = height / image_height;
+
+    match fit {
+        ContentFit::Fill => (scale_x, scale_y),
+        ContentFit::Contain => {
+            let scale = scale_x.min(scale_y);
+            (scale, scale)
+        }
+        ContentFit::Cover => {
+            let scale = scale_x.max(scale_y);
+            (scale, scale)
+        }
+        ContentFit::ScaleDown => {
+            let scale = scale_x.min(scale_y).min(1.0);
+            (scale, scale)
+        }
+        ContentFit::None => (1.0, 1.0),
+    }
+}
 
 pub fn draw_image<T: PaintScene>(
     scene: &mut T,
+    transform: Affine,
     image_brush: ImageBrush<&ImageData>,
     x: f64,
     y: f64,
+    width: f64,
+    height: f64,
+    fit: ContentFit,
+    crop: Option<CropRect>,
+    opacity: f32,
+    tint: Option<(u8, u8, u8, u8)>,
 ) {
-    let transform = Affine::translate(Vec2::new(x, y));
-    scene.draw_image(image_brush, transform);
+    let image = image_brush.image;
+
+    // Part of the image that is shown
+    let source = match crop {
+        Some(crop) => Rect::new(
+            crop.x as f64,
+            crop.y as f64,
+            (crop.x + crop.width) as f64,
+            (crop.y + crop.height) as f64,
+        )
+        .intersect(Rect::new(0.0, 0.0, image.width as f64, image.height as f64)),
+        None => Rect::new(0.0, 0.0, image.width as f64, image.height as f64),
+    };
+    if source.width() <= 0.0 || source.height() <= 0.0 || opacity <= 0.0 {
+        return;
+    }
+
+    let (scale_x, scale_y) = fit_scale(fit, width, height, source.width(), source.height());
+
+    // Center the scaled image inside the size
+    let drawn_width = source.width() * scale_x;
+    let drawn_height = source.height() * scale_y;
+    let drawn_x = x + (width - drawn_width) / 2.0;
+    let drawn_y = y + (height - drawn_height) / 2.0;
+
+    let image_transform = transform
+        * Affine::translate(Vec2::new(drawn_x, drawn_y))
+        * Affine::scale_non_uniform(scale_x, scale_y)
+        * Affine::translate(Vec2::new(-source.x0, -source.y0));
+
+    // Visible area, overflow of cover, none and the cropped out parts are cut off
+    let clip = Rect::new(x, y, x + width, y + height).intersect(Rect::new(
+        drawn_x,
+        drawn_y,
+        drawn_x + drawn_width,
+        drawn_y + drawn_height,
+    ));
+
+    let overflows = drawn_width > width || drawn_height > height;
+    let layered = crop.is_some() || overflows || opacity < 1.0 || tint.is_some();
+
+    if !layered {
+        scene.draw_image(image_brush, image_transform);
+        return;
+    }
+
+    // Draw image in own layer so tint only affects the image
+    scene.push_layer(Mix::Normal, opacity, transform, &clip);
+    scene.draw_image(image_brush, image_transform);
+
+    if let Some(tint) = tint {
+        let color = Color::from_rgba8(tint.0, tint.1, tint.2, tint.3);
+        scene.push_layer(
+            BlendMode::new(Mix::Multiply, Compose::SrcAtop),
+            1.0,
+            transform,
+            &clip,
+        );
+        scene.fill(Fill::NonZero, transform, color, None, &clip);
+        scene.pop_layer();
+    }
+
+    scene.pop_layer();
 }
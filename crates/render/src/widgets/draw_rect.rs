@@ -1,11 +1,13 @@
-use kurbo::{Affine, RoundedRect};
+use glazeui_core::{Background, GradientStop, Radius};
+use kurbo::{Affine, Point, RoundedRect, RoundedRectRadii};
 use multirender::PaintScene;
-use peniko::Color;
+use peniko::{Color, Gradient, Mix};
 
 pub fn draw_rectangle<T: PaintScene>(
     scene: &mut T,
-    radius: f64,
-    color: &(u8, u8, u8, u8),
+    transform: Affine,
+    radius: impl Into<RoundedRectRadii>,
+    background: &Background,
     x: f64,
     y: f64,
     width: f64,
@@ -13,12 +15,100 @@ pub fn draw_rectangle<T: PaintScene>(
 ) {
     // Draw a rectangle
     let rect = RoundedRect::new(x, y, x + width, y + height, radius);
-    let pen_color = Color::from_rgba8(color.0, color.1, color.2, color.3);
-    scene.fill(
-        peniko::Fill::NonZero,
-        Affine::IDENTITY,
-        pen_color,
-        None,
-        &rect,
-    );
+
+    match gradient(background, x, y, width, height) {
+        Some(gradient) => {
+            scene.fill(peniko::Fill::NonZero, transform, &gradient, None, &rect);
+        }
+        None => {
+            if let Background::Color(color) = background {
+                let pen_color = Color::from_rgba8(color.r, color.g, color.b, color.a);
+                scene.fill(peniko::Fill::NonZero, transform, pen_color, None, &rect);
+            }
+        }
+    }
+}
+
+/// Kurbo radii of the corners
+pub fn radii(radius: &Radius) -> RoundedRectRadii {
+    RoundedRectRadii::new(
+        radius.top_left as f64,
+        radius.top_right as f64,
+        radius.bottom_right as f64,
+        radius.bottom_left as f64,
+    )
+}
+
+/// Mask everything drawn until `pop_layer` to the rounded rectangle
+pub fn push_clip<T: PaintScene>(
+    scene: &mut T,
+    transform: Affine,
+    radius: impl Into<RoundedRectRadii>,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) {
+    let rect = RoundedRect::new(x, y, x + width, y + height, radius);
+    scene.push_layer(Mix::Normal, 1.0, transform, &rect);
+}
+
+/// Peniko gradient of the background positioned on the rectangle, `None` for a solid color
+fn gradient(background: &Background, x: f64, y: f64, width: f64, height: f64) -> Option<Gradient> {
+    let center_of =
+        |center: &(f32, f32)| Point::new(x + width * center.0 as f64, y + height * center.1 as f64);
+
+    let gradient = match background {
+        Background::Color(_) => return None,
+        Background::LinearGradient { angle, stops } => {
+            // Line through the center, long enough for the gradient to reach the corners
+            let (sin, cos) = (*angle as f64).to_radians().sin_cos();
+            let half_length = (width * cos.abs() + height * sin.abs()) / 2.0;
+            let center = Point::new(x + width / 2.0, y + height / 2.0);
+            let offset = (cos * half_length, sin * half_length);
+
+            Gradient::new_linear(
+                (center.x - offset.0, center.y - offset.1),
+                (center.x + offset.0, center.y + offset.1),
+            )
+            .with_stops(color_stops(stops).as_slice())
+        }
+        Background::RadialGradient {
+            center,
+            radius,
+            stops,
+        } => {
+            let center = center_of(center);
+            // Distance to the farthest corner
+            let dx = (center.x - x).max(x + width - center.x);
+            let dy = (center.y - y).max(y + height - center.y);
+            let farthest = (dx * dx + dy * dy).sqrt();
+
+            Gradient::new_radial(center, (farthest * *radius as f64) as f32)
+                .with_stops(color_stops(stops).as_slice())
+        }
+        Background::SweepGradient {
+            center,
+            angle,
+            stops,
+        } => {
+            let start = angle.to_radians();
+            Gradient::new_sweep(center_of(center), start, start + std::f32::consts::TAU)
+                .with_stops(color_stops(stops).as_slice())
+        }
+    };
+    Some(gradient)
+}
+
+fn color_stops(stops: &[GradientStop]) -> Vec<(f32, Color)> {
+    stops
+        .iter()
+        .map(|stop| {
+            let color = stop.color;
+            (
+                stop.offset,
+                Color::from_rgba8(color.r, color.g, color.b, color.a),
+            )
+        })
+        .collect()
 }
@@ -0,0 +1,33 @@
+use glazeui_layout::measure::text::TextBrush;
+use kurbo::Affine;
+use multirender::PaintScene;
+use parley::{Layout, PositionedLayoutItem};
+
+use crate::widgets::draw_text::draw_glyph_run;
+
+/// Draw rich text shaped by the layout engine, `hovered_span` is drawn with `hover_color`
+///
+/// `transform` places the text on the scene
+pub fn draw_rich_text<T: PaintScene>(
+    scene: &mut T,
+    transform: Affine,
+    layout: &Layout<TextBrush>,
+    hovered_span: Option<usize>,
+    hover_color: (u8, u8, u8, u8),
+) {
+    for line in layout.lines() {
+        for item in line.items() {
+            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+
+            let brush = glyph_run.style().brush;
+            let color = if hovered_span == Some(brush.span) {
+                [hover_color.0, hover_color.1, hover_color.2, hover_color.3]
+            } else {
+                brush.color
+            };
+            draw_glyph_run(scene, &glyph_run, color, transform);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use glazeui_core::{Radius, Shadow};
+use kurbo::{Affine, Rect, Vec2};
+use multirender::PaintScene;
+use peniko::{BlendMode, Color, Compose, Fill, Mix};
+
+use crate::widgets::draw_rect::{push_clip, radii};
+
+/// Draw the shadows that are outside of the widget, before its background
+pub fn draw_drop_shadows<T: PaintScene>(
+    scene: &mut T,
+    transform: Affine,
+    shadows: &[Shadow],
+    radius: &Radius,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) {
+    for shadow in shadows.iter().filter(|shadow| !shadow.inset) {
+        let spread = shadow.spread as f64;
+        let rect = Rect::new(x, y, x + width, y + height).inset(spread) + offset(shadow);
+        if rect.width() <= 0.0 || rect.height() <= 0.0 {
+            continue;
+        }
+
+        scene.draw_box_shadow(
+            transform,
+            rect,
+            color(shadow),
+            (shadow_radius(radius) + spread).max(0.0),
+            std_dev(shadow),
+        );
+    }
+}
+
+/// Draw the shadows that are inside of the widget, over its background
+pub fn draw_inset_shadows<T: PaintScene>(
+    scene: &mut T,
+    transform: Affine,
+    shadows: &[Shadow],
+    radius: &Radius,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) {
+    let rect = Rect::new(x, y, x + width, y + height);
+
+    for shadow in shadows.iter().filter(|shadow| shadow.inset) {
+        let spread = shadow.spread as f64;
+        // Part of the widget the shadow doesn't cover
+        let hole = rect.inset(-spread) + offset(shadow);
+
+        // Fill the widget with the shadow and cut the blurred hole out of it
+        push_clip(scene, transform, radii(radius), x, y, width, height);
+        scene.fill(Fill::NonZero, transform, color(shadow), None, &rect);
+        if hole.width() > 0.0 && hole.height() > 0.0 {
+            scene.push_layer(
+                BlendMode::new(Mix::Normal, Compose::DestOut),
+                1.0,
+                transform,
+                &rect,
+            );
+            scene.draw_box_shadow(
+                transform,
+                hole,
+                Color::BLACK,
+                (shadow_radius(radius) - spread).max(0.0),
+                std_dev(shadow),
+            );
+            scene.pop_layer();
+        }
+        scene.pop_layer();
+    }
+}
+
+/// Area the rectangle and its drop shadows cover, blur included
+pub fn drop_shadows_bounds(shadows: &[Shadow], rect: Rect) -> Rect {
+    shadows
+        .iter()
+        .filter(|shadow| !shadow.inset)
+        .fold(rect, |bounds, shadow| {
+            let reach = (shadow.blur_radius * 1.5 + shadow.spread.max(0.0)) as f64;
+            bounds.union(rect.inset(reach) + offset(shadow))
+        })
+}
+
+fn offset(shadow: &Shadow) -> Vec2 {
+    Vec2::new(shadow.offset.0 as f64, shadow.offset.1 as f64)
+}
+
+fn color(shadow: &Shadow) -> Color {
+    Color::from_rgba8(
+        shadow.color.r,
+        shadow.color.g,
+        shadow.color.b,
+        shadow.color.a,
+    )
+}
+
+/// Box shadows have one radius for all corners, the largest one is used
+fn shadow_radius(radius: &Radius) -> f64 {
+    radius
+        .top_left
+        .max(radius.top_right)
+        .max(radius.bottom_right)
+        .max(radius.bottom_left) as f64
+}
+
+/// Blur radius is twice the standard deviation, like in CSS
+fn std_dev(shadow: &Shadow) -> f64 {
+    shadow.blur_radius as f64 / 2.0
+}
@@ -1,3 +1,6 @@
+pub mod draw_border;
 pub mod draw_image;
 pub mod draw_rect;
+pub mod draw_rich_text;
+pub mod draw_shadow;
 pub mod draw_text;
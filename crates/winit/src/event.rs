@@ -1,4 +1,6 @@
 #[derive(Debug)]
 pub enum UserEvent<M> {
     Message(M),
+    /// Image finished decoding in background
+    ImageLoaded,
 }
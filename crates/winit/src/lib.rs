@@ -1,16 +1,19 @@
 #[cfg(feature = "async")]
 use glazeui_core::task::Task;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub mod event;
 
 #[cfg(feature = "async")]
 use crate::event::UserEvent;
-use glazeui_core::{Backend, Color, Widget, window::Window};
-use glazeui_layout::LayoutEngine;
+use glazeui_core::{Backend, Background, Widget, window::Window};
+use glazeui_layout::{LayoutEngine, measure::text::TextBrush};
 use glazeui_render::{
-    RenderState, Renderer as GlazeuiRenderer, draw::draw, widgets::draw_rect::draw_rectangle,
+    RenderState, Renderer as GlazeuiRenderer, damage::DamageTracker, draw::draw,
+    widgets::draw_rect::draw_rectangle,
 };
+use kurbo::Affine;
 use multirender::{PaintScene, WindowRenderer};
 #[cfg(feature = "skia")]
 use multirender_skia::SkiaWindowRenderer;
@@ -24,7 +27,7 @@ use parley::{FontContext, LayoutContext};
 #[cfg(feature = "async")]
 use winit::event_loop::EventLoopProxy;
 use winit::{
-    dpi::PhysicalPosition,
+    dpi::LogicalPosition,
     event_loop::ActiveEventLoop,
     window::{Window as WinitWindow, WindowAttributes},
 };
@@ -38,8 +41,11 @@ pub struct Application<M: Clone + Send + 'static, App: 'static> {
     pub update_fn: fn(&mut App, M, &mut Window) -> Task<M>,
     #[cfg(not(feature = "async"))]
     pub update_fn: fn(&mut App, M, &mut Window),
-    pub background: Color,
-    pub position: PhysicalPosition<f64>,
+    pub background: Background,
+    /// Cursor position in logical pixels, like the layout
+    pub position: LogicalPosition<f64>,
+    /// Link under the cursor (widget id, span index)
+    pub hovered_link: Option<(u64, usize)>,
 }
 
 pub struct Renderer<M: Clone + Send + 'static> {
@@ -48,8 +54,16 @@ pub struct Renderer<M: Clone + Send + 'static> {
     pub fallback_backend: Backend,
     pub font_context: FontContext,
     pub registred_fallback_font: bool,
-    pub layout_context: LayoutContext,
+    pub layout_context: LayoutContext<TextBrush>,
     pub layout: LayoutEngine<M>,
+    /// Widgets of the last drawn frame, frames without changes aren't drawn
+    pub damage: DamageTracker,
+    /// View of the last frame, drawn again for the next frame of animated images
+    pub frame: Option<Widget<M>>,
+    /// Next redraw only shows the next frame of animated images, the view didn't change
+    pub animation_frame: bool,
+    /// Time the event loop waits for to draw the next frame of animated images
+    pub next_animation_frame: Option<Instant>,
 }
 
 pub struct Program<M: Clone + Send + 'static, App: 'static> {
@@ -85,36 +99,35 @@ impl<M: Clone + Send + 'static, App: 'static> Program<M, App> {
 
     fn draw_scene<T: PaintScene>(
         scene: &mut T,
-        font_context: &mut FontContext,
-        registred_fallback_font: bool,
-        layout_context: &mut LayoutContext,
-        layout_engine: &mut LayoutEngine<M>,
-        scale: f32,
+        layout_engine: &LayoutEngine<M>,
+        hovered_link: Option<(u64, usize)>,
         widget: &Widget<M>,
-        background: Color,
+        background: &Background,
         window_size: (u32, u32),
+        scale: f64,
+        time: Instant,
     ) {
         scene.reset();
-        let color = &(background.r, background.g, background.b, background.a);
 
         draw_rectangle(
             scene,
+            Affine::IDENTITY,
             0.0,
-            color,
+            background,
             0.0,
             0.0,
             window_size.0 as f64,
             window_size.1 as f64,
         ); // Background
 
+        // Layout is in logical pixels, the scene in physical pixels
         draw(
             scene,
-            font_context,
-            registred_fallback_font,
-            layout_context,
             layout_engine,
-            scale,
+            hovered_link,
             widget,
+            Affine::scale(scale),
+            time,
         );
     }
 
@@ -136,6 +149,13 @@ impl<M: Clone + Send + 'static, App: 'static> Program<M, App> {
             )
         });
         self.window = Some(window.clone());
+        // New surface has nothing on it yet
+        self.renderer.damage.invalidate();
+
+        // Window can be created with a different size than asked, e.g. on HiDPI displays
+        let size = window.inner_size();
+        self.width = size.width;
+        self.height = size.height;
 
         if match &mut renderer {
             #[cfg(feature = "cpu")]
@@ -1,9 +1,13 @@
 #[cfg(feature = "async")]
 use glazeui_core::task::Task;
+use std::time::Instant;
 
-use glazeui_core::{Widget, WidgetElement, id::clear_counter, window::Window as UserWindow};
+use glazeui_core::{
+    Position, Widget, WidgetElement, id::clear_counter, window::Window as UserWindow,
+};
 use glazeui_layout::{LayoutEngine, LayoutNode};
-use glazeui_render::{RenderState, Renderer};
+use glazeui_render::{RenderState, Renderer, damage::Damage, transform::widget_affine};
+use kurbo::Point;
 use multirender::WindowRenderer;
 #[cfg(feature = "skia")]
 use multirender_skia::SkiaWindowRenderer;
@@ -17,10 +21,10 @@ use multirender_vello_hybrid::VelloHybridWindowRenderer;
 use winit::event_loop::EventLoopProxy;
 use winit::{
     application::ApplicationHandler,
-    dpi::PhysicalPosition,
-    event::{ElementState, MouseButton, WindowEvent as WinitWindowEvent},
-    event_loop::ActiveEventLoop,
-    window::WindowId,
+    dpi::LogicalPosition,
+    event::{ElementState, MouseButton, StartCause, WindowEvent as WinitWindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow},
+    window::{CursorIcon, WindowId},
 };
 
 use crate::{Program, event::UserEvent};
@@ -47,9 +51,22 @@ impl<M: Clone + Send + 'static, App> ApplicationHandler<UserEvent<M>> for Progra
         self.set_backend(renderer, &fallback_backend, event_loop);
     }
 
+    fn new_events(&mut self, _event_loop: &ActiveEventLoop, cause: StartCause) {
+        // Time for the next frame of an animated image
+        if let StartCause::ResumeTimeReached { .. } = cause {
+            if self.renderer.next_animation_frame.is_some() {
+                self.renderer.animation_frame = true;
+                self.request_redraw();
+            }
+        }
+    }
+
     fn user_event(&mut self, _event_loop: &ActiveEventLoop, _event: UserEvent<M>) {
-        #[cfg(feature = "async")]
+        // Messages and loaded images change the view
+        self.renderer.animation_frame = false;
+
         match _event {
+            #[cfg(feature = "async")]
             UserEvent::Message(message) => {
                 if let Some(window) = self.window.as_ref() {
                     // Create copy of window and give that to user, with that he can edit the window settings
@@ -78,6 +95,10 @@ impl<M: Clone + Send + 'static, App> ApplicationHandler<UserEvent<M>> for Progra
                     }
                 }
             }
+            #[cfg(not(feature = "async"))]
+            UserEvent::Message(_) => {}
+            // Image decoded in background is ready to be drawn
+            UserEvent::ImageLoaded => self.request_redraw(),
         }
     }
 
@@ -103,110 +124,152 @@ impl<M: Clone + Send + 'static, App> ApplicationHandler<UserEvent<M>> for Progra
                 self.width = physical_size.width;
                 self.height = physical_size.height;
                 renderer.set_size(self.width, self.height);
+                self.renderer.damage.invalidate();
+                self.renderer.animation_frame = false;
                 self.request_redraw();
             }
             WinitWindowEvent::RedrawRequested => {
-                // Remove all id's that was created in the past
-                clear_counter();
-
-                // Create copy of window and give that to user, with that he can edit the window settings
-                let mut user_window = UserWindow {
-                    window: window.clone(),
-                    background: &mut self.application.background,
-                    eventloop: event_loop,
-                };
+                let scale = window.scale_factor();
 
-                let mut layout = LayoutEngine::new();
-                let view_fn = self.application.view_fn;
-                let ui = view_fn(&mut self.application.user_struct, &mut user_window);
+                // Next frame of animated images only has to be drawn, the view and its layout are the same
+                let last_frame = self.renderer.frame.take();
+                let ui = match last_frame {
+                    Some(ui) if self.renderer.animation_frame => ui,
+                    _ => {
+                        // Remove all id's that was created in the past
+                        clear_counter();
 
-                let scale = window.scale_factor();
+                        // Create copy of window and give that to user, with that he can edit the window settings
+                        let mut user_window = UserWindow {
+                            window: window.clone(),
+                            background: &mut self.application.background,
+                            eventloop: event_loop,
+                        };
 
-                // Compute layout
-                layout.compute(
+                        let view_fn = self.application.view_fn;
+                        let ui = view_fn(&mut self.application.user_struct, &mut user_window);
+
+                        // Compute layout, the engine keeps shaped text of the last frames
+                        self.renderer.layout.compute(
+                            &ui,
+                            self.width as f32 / scale as f32,
+                            self.height as f32 / scale as f32,
+                            scale as f32,
+                            self.renderer.registred_fallback_font,
+                            &mut self.renderer.font_context,
+                            &mut self.renderer.layout_context,
+                        );
+                        ui
+                    }
+                };
+                self.renderer.animation_frame = false;
+
+                // One time for the whole frame, so all animated images agree on their frame
+                let now = Instant::now();
+
+                // Frames without changes aren't drawn, the last one stays on screen
+                let damage = self.renderer.damage.update(
+                    &self.renderer.layout,
+                    self.application.hovered_link,
                     &ui,
-                    self.width as f32 / scale as f32,
-                    self.height as f32 / scale as f32,
-                    &mut self.renderer.font_context,
-                    &mut self.renderer.layout_context,
+                    &self.application.background,
+                    now,
                 );
 
-                self.renderer.layout = layout;
-
+                // The whole frame is drawn when anything changed, a presented surface
+                // doesn't keep the pixels of the last frame
                 match renderer {
+                    _ if damage == Damage::None => {}
                     #[cfg(feature = "skia")]
                     Renderer::Skia(r) => r.render(|p| {
                         Self::draw_scene(
                             p,
-                            &mut self.renderer.font_context,
-                            self.renderer.registred_fallback_font,
-                            &mut self.renderer.layout_context,
-                            &mut self.renderer.layout,
-                            1.0,
+                            &self.renderer.layout,
+                            self.application.hovered_link,
                             &ui,
-                            self.application.background,
+                            &self.application.background,
                             (self.width, self.height),
+                            scale,
+                            now,
                         );
                     }),
                     #[cfg(feature = "cpu")]
                     Renderer::CpuSoftbuffer(r) => r.render(|p| {
                         Self::draw_scene(
                             p,
-                            &mut self.renderer.font_context,
-                            self.renderer.registred_fallback_font,
-                            &mut self.renderer.layout_context,
-                            &mut self.renderer.layout,
-                            1.0,
+                            &self.renderer.layout,
+                            self.application.hovered_link,
                             &ui,
-                            self.application.background,
+                            &self.application.background,
                             (self.width, self.height),
+                            scale,
+                            now,
                         );
                     }),
                     #[cfg(feature = "vello")]
                     Renderer::Gpu(r) => r.render(|p| {
                         Self::draw_scene(
                             p,
-                            &mut self.renderer.font_context,
-                            self.renderer.registred_fallback_font,
-                            &mut self.renderer.layout_context,
-                            &mut self.renderer.layout,
-                            1.0,
+                            &self.renderer.layout,
+                            self.application.hovered_link,
                             &ui,
-                            self.application.background,
+                            &self.application.background,
                             (self.width, self.height),
+                            scale,
+                            now,
                         );
                     }),
                     #[cfg(feature = "hybrid")]
                     Renderer::Hybrid(r) => r.render(|p| {
                         Self::draw_scene(
                             p,
-                            &mut self.renderer.font_context,
-                            self.renderer.registred_fallback_font,
-                            &mut self.renderer.layout_context,
-                            &mut self.renderer.layout,
-                            1.0,
+                            &self.renderer.layout,
+                            self.application.hovered_link,
                             &ui,
-                            self.application.background,
+                            &self.application.background,
                             (self.width, self.height),
+                            scale,
+                            now,
                         );
                     }),
                     Renderer::Null(r) => r.render(|p| {
                         Self::draw_scene(
                             p,
-                            &mut self.renderer.font_context,
-                            self.renderer.registred_fallback_font,
-                            &mut self.renderer.layout_context,
-                            &mut self.renderer.layout,
-                            1.0,
+                            &self.renderer.layout,
+                            self.application.hovered_link,
                             &ui,
-                            self.application.background,
+                            &self.application.background,
                             (self.width, self.height),
+                            scale,
+                            now,
                         );
                     }),
                 };
+
+                // Wake up for the next frame of visible animated images
+                let next_frame = next_animation_frame(
+                    &ui,
+                    &self.renderer.layout,
+                    self.width as f32 / scale as f32,
+                    self.height as f32 / scale as f32,
+                    now,
+                );
+                // Control flow is only changed while an animation is on screen
+                match next_frame {
+                    Some(time) => event_loop.set_control_flow(ControlFlow::WaitUntil(time)),
+                    None if self.renderer.next_animation_frame.is_some() => {
+                        event_loop.set_control_flow(ControlFlow::Wait)
+                    }
+                    None => {}
+                }
+                self.renderer.next_animation_frame = next_frame;
+                self.renderer.frame = Some(ui);
             }
             WinitWindowEvent::MouseInput { state, button, .. } => {
                 if button == MouseButton::Left && state == ElementState::Pressed {
+                    // Message of the click changes the view
+                    self.renderer.animation_frame = false;
+
                     if let Some(window) = self.window.as_ref() {
                         // Remove all id's that was created in the past
                         clear_counter();
@@ -238,8 +301,42 @@ impl<M: Clone + Send + 'static, App> ApplicationHandler<UserEvent<M>> for Progra
                     }
                 }
             }
+            WinitWindowEvent::ScaleFactorChanged { .. } => {
+                // Text is shaped again at the new scale, the new size comes with `Resized`
+                self.renderer.damage.invalidate();
+                self.renderer.animation_frame = false;
+                self.request_redraw();
+            }
+            WinitWindowEvent::Occluded(false) => {
+                // Content of the window may be gone while it was hidden
+                self.renderer.damage.invalidate();
+                self.request_redraw();
+            }
             WinitWindowEvent::CursorMoved { position, .. } => {
+                // Layout is in logical pixels
+                let position = position.to_logical::<f64>(window.scale_factor());
                 self.application.position = position;
+
+                // Check if cursor is over a link of the last drawn view
+                let hovered_link = self.renderer.frame.as_ref().and_then(|ui| {
+                    link_at(
+                        ui,
+                        &self.renderer.layout,
+                        Point::new(position.x, position.y),
+                    )
+                });
+
+                if hovered_link != self.application.hovered_link {
+                    self.application.hovered_link = hovered_link;
+
+                    let cursor = if hovered_link.is_some() {
+                        CursorIcon::Pointer
+                    } else {
+                        CursorIcon::Default
+                    };
+                    window.set_cursor(cursor);
+                    window.request_redraw();
+                }
             }
             _ => (),
         }
@@ -252,7 +349,7 @@ fn check_click<M: Clone + Send + 'static, App>(
     #[cfg(feature = "async")] runtime: &tokio::runtime::Runtime,
     render_state: &RenderState,
     layout: &LayoutEngine<M>,
-    pos: &PhysicalPosition<f64>,
+    pos: &LogicalPosition<f64>,
     user_struct: &mut App,
     #[cfg(feature = "async")] proxy: EventLoopProxy<UserEvent<M>>,
     #[cfg(feature = "async")] user_update: &fn(&mut App, M, &mut UserWindow) -> Task<M>,
@@ -261,121 +358,195 @@ fn check_click<M: Clone + Send + 'static, App>(
     // Get root widget info
     let layout_resolved = layout.get(ui.id).unwrap();
 
+    // Move the click into the space of the widget before its transform
+    let transformed;
+    let pos = match &ui.style.transform {
+        Some(transform) => {
+            let point =
+                widget_affine(transform, layout_resolved).inverse() * Point::new(pos.x, pos.y);
+            transformed = LogicalPosition::new(point.x, point.y);
+            &transformed
+        }
+        None => pos,
+    };
+
     // Check if was a click inside the root widget
     let clicked = check_click_inside(layout_resolved, *pos);
 
-    if clicked {
-        // If root widget is VStack or HStack
-        if let WidgetElement::VStack { children, .. } | WidgetElement::HStack { children, .. } =
-            &ui.element
-        {
-            // Go to every child in vstack/hstack childrens
-            for child in children {
-                check_click(
-                    window,
-                    child,
-                    #[cfg(feature = "async")]
-                    runtime,
-                    render_state,
-                    layout,
-                    pos,
-                    user_struct,
-                    #[cfg(feature = "async")]
-                    proxy.clone(),
-                    user_update,
-                );
-            }
-        } else if let WidgetElement::Container { child, .. } = &ui.element {
-            check_click(
-                window,
-                child,
-                #[cfg(feature = "async")]
-                runtime,
-                render_state,
-                layout,
-                pos,
-                user_struct,
-                #[cfg(feature = "async")]
-                proxy.clone(),
-                user_update,
-            );
-
-            // Get widget information (position, width and height)
-            let layout_resolved = layout.get(ui.id).unwrap();
-            // Check if was a click inside the widget
-            let clicked = check_click_inside(layout_resolved, *pos);
-            if clicked {
-                // If click was inside the widget and user provided a fn in on_press
-                if let Some(callback) = &ui.on_press {
-                    // Call update fn
-                    let _task = user_update(user_struct, callback.clone(), window);
-
-                    #[cfg(feature = "async")]
-                    if let Some(future) = _task.future {
-                        runtime.spawn(async move {
-                            let message = future.await;
-                            proxy.send_event(UserEvent::Message(message)).ok();
-                        });
-                    }
+    let children = match &ui.element {
+        WidgetElement::VStack { children, .. }
+        | WidgetElement::HStack { children, .. }
+        | WidgetElement::Wrap { children, .. } => children.as_slice(),
+        WidgetElement::Container { child, .. } => std::slice::from_ref(child.as_ref()),
+        _ => &[],
+    };
 
-                    // Redraw the window
-                    let window = match render_state {
-                        RenderState::Active { window, renderer } => {
-                            if renderer.is_active() {
-                                Some(window)
-                            } else {
-                                None
-                            }
-                        }
-                        RenderState::Suspended(_) => None,
-                    };
+    // Absolute children are placed over the flow and can be outside of the parent box,
+    // check them first and even when the click is outside of the parent
+    let (absolute, flow): (Vec<&Widget<M>>, Vec<&Widget<M>>) = children
+        .iter()
+        .partition(|child| matches!(child.style.position, Position::Absolute(_)));
+    let flow = if clicked { flow } else { Vec::new() };
 
-                    if let Some(window) = window {
-                        window.request_redraw();
-                    }
+    for child in absolute.into_iter().chain(flow) {
+        check_click(
+            window,
+            child,
+            #[cfg(feature = "async")]
+            runtime,
+            render_state,
+            layout,
+            pos,
+            user_struct,
+            #[cfg(feature = "async")]
+            proxy.clone(),
+            user_update,
+        );
+    }
+
+    if !clicked {
+        return;
+    }
+
+    let message = match &ui.element {
+        WidgetElement::Container { .. } | WidgetElement::Label { .. } => ui.on_press.clone(),
+        WidgetElement::RichText { spans, on_link, .. } => {
+            // Check if a link span was clicked
+            let link = match layout.link_at(pos.x as f32, pos.y as f32) {
+                Some((id, span)) if id == ui.id => {
+                    spans.get(span).and_then(|span| span.link.clone())
                 }
+                _ => None,
+            };
+
+            match (link, on_link) {
+                (Some(url), Some(on_link)) => Some(on_link(url)),
+                _ => ui.on_press.clone(),
             }
-        } else if let WidgetElement::Label { .. } = ui.element {
-            // Get widget information (position, width and height)
-            let layout_resolved = layout.get(ui.id).unwrap();
-            // Check if was a click inside the widget
-            let clicked = check_click_inside(layout_resolved, *pos);
-            if clicked {
-                // If click was inside the widget and user provided a fn in on_press
-                if let Some(callback) = &ui.on_press {
-                    // Call update fn
-                    let _task = user_update(user_struct, callback.clone(), window);
-
-                    #[cfg(feature = "async")]
-                    if let Some(future) = _task.future {
-                        runtime.spawn(async move {
-                            let message = future.await;
-                            proxy.send_event(UserEvent::Message(message)).ok();
-                        });
-                    }
+        }
+        _ => None,
+    };
 
-                    // Redraw the window
-                    let window = match render_state {
-                        RenderState::Active { window, renderer } => {
-                            if renderer.is_active() {
-                                Some(window)
-                            } else {
-                                None
-                            }
-                        }
-                        RenderState::Suspended(_) => None,
-                    };
+    // If click was inside the widget and user provided a fn in on_press
+    if let Some(message) = message {
+        send_message(
+            window,
+            message,
+            #[cfg(feature = "async")]
+            runtime,
+            render_state,
+            user_struct,
+            #[cfg(feature = "async")]
+            proxy.clone(),
+            user_update,
+        );
+    }
+}
 
-                    if let Some(window) = window {
-                        window.request_redraw();
-                    }
-                }
+/// Link (widget id, span index) at the position, moved through widget transforms like a click
+fn link_at<M: Clone + Send + 'static>(
+    ui: &Widget<M>,
+    layout: &LayoutEngine<M>,
+    pos: Point,
+) -> Option<(u64, usize)> {
+    let node = layout.get(ui.id)?;
+    let pos = match &ui.style.transform {
+        Some(transform) => widget_affine(transform, node).inverse() * pos,
+        None => pos,
+    };
+
+    match &ui.element {
+        WidgetElement::RichText { .. } => layout
+            .link_at(pos.x as f32, pos.y as f32)
+            .filter(|(id, _)| *id == ui.id),
+        WidgetElement::Container { child, .. } => link_at(child, layout, pos),
+        WidgetElement::VStack { children }
+        | WidgetElement::HStack { children }
+        | WidgetElement::Wrap { children, .. } => children
+            .iter()
+            .find_map(|child| link_at(child, layout, pos)),
+        _ => None,
+    }
+}
+
+/// Earliest time an animated image inside the visible area needs its next frame
+fn next_animation_frame<M: Clone + Send + 'static>(
+    ui: &Widget<M>,
+    layout: &LayoutEngine<M>,
+    width: f32,
+    height: f32,
+    now: Instant,
+) -> Option<Instant> {
+    match &ui.element {
+        WidgetElement::Image {
+            animation: Some(animation),
+            ..
+        } => {
+            let node = layout.get(ui.id)?;
+            let visible = node.x < width
+                && node.y < height
+                && node.x + node.width > 0.0
+                && node.y + node.height > 0.0;
+
+            if visible {
+                animation.next_frame_at(now)
+            } else {
+                None
+            }
+        }
+        WidgetElement::Container { child, .. } => {
+            next_animation_frame(child, layout, width, height, now)
+        }
+        WidgetElement::VStack { children }
+        | WidgetElement::HStack { children }
+        | WidgetElement::Wrap { children, .. } => children
+            .iter()
+            .filter_map(|child| next_animation_frame(child, layout, width, height, now))
+            .min(),
+        _ => None,
+    }
+}
+
+/// Give message to update fn, spawn its task and redraw the window
+fn send_message<M: Clone + Send + 'static, App>(
+    window: &mut UserWindow,
+    message: M,
+    #[cfg(feature = "async")] runtime: &tokio::runtime::Runtime,
+    render_state: &RenderState,
+    user_struct: &mut App,
+    #[cfg(feature = "async")] proxy: EventLoopProxy<UserEvent<M>>,
+    #[cfg(feature = "async")] user_update: &fn(&mut App, M, &mut UserWindow) -> Task<M>,
+    #[cfg(not(feature = "async"))] user_update: &fn(&mut App, M, &mut UserWindow),
+) {
+    // Call update fn
+    let _task = user_update(user_struct, message, window);
+
+    #[cfg(feature = "async")]
+    if let Some(future) = _task.future {
+        runtime.spawn(async move {
+            let message = future.await;
+            proxy.send_event(UserEvent::Message(message)).ok();
+        });
+    }
+
+    // Redraw the window
+    let window = match render_state {
+        RenderState::Active { window, renderer } => {
+            if renderer.is_active() {
+                Some(window)
+            } else {
+                None
             }
         }
+        RenderState::Suspended(_) => None,
+    };
+
+    if let Some(window) = window {
+        window.request_redraw();
     }
 }
 
-fn check_click_inside(layout: &LayoutNode, click: PhysicalPosition<f64>) -> bool {
+fn check_click_inside(layout: &LayoutNode, click: LogicalPosition<f64>) -> bool {
     if click.x >= layout.x as f64
         && click.x <= layout.x as f64 + layout.width as f64
         && click.y >= layout.y as f64
@@ -1,7 +1,7 @@
 use glazeui::{
     application::start,
     core::{
-        Color, Widget, button, hstack, spacer, vstack,
+        Color, Margin, Widget, button, hstack, vstack,
         window::{self, Window},
     },
 };
@@ -62,15 +62,12 @@ impl ArrayCount {
             .on_press(Message::AddDecrementCounter)
             .build();
 
-        // Right now, we use a spacing widget because the layout engine doesn't support margins or padding
-        let spacing_left = spacer().width(10).build();
-        let spacing_top = spacer().height(10).build();
-        let space_add_increment = hstack!(spacer().width(20).build(), add_increment).build();
-        let space_add_decrement = hstack!(spacer().width(20).build(), add_decrement).build();
-
-        let add_buttons = hstack!(space_add_increment, space_add_decrement).build();
+        let add_buttons = hstack!(add_increment, add_decrement)
+            .spacing(20)
+            .margin(Margin::new().left(20))
+            .build();
 
-        let mut increment_buttons = hstack!(spacing_left.clone()).spacing(10);
+        let mut increment_buttons = hstack!().spacing(10).margin(Margin::new().left(20));
         for _index in 0..self.increment_len_counters {
             let new_button = button(&self.count.to_string())
                 .radius(360)
@@ -84,7 +81,7 @@ impl ArrayCount {
             increment_buttons.push(new_button);
         }
 
-        let mut decrement_buttons = hstack!(spacing_left).spacing(10);
+        let mut decrement_buttons = hstack!().spacing(10).margin(Margin::new().left(20));
         for _index in 0..self.decrement_len_counters {
             let new_button = button(&self.count.to_string())
                 .radius(360)
@@ -99,12 +96,12 @@ impl ArrayCount {
         }
 
         vstack!(
-            spacing_top,
             add_buttons,
             increment_buttons.build(),
             decrement_buttons.build()
         )
         .spacing(20)
+        .margin(Margin::new().top(10))
         .build()
     }
 }
@@ -0,0 +1,47 @@
+use glazeui::{
+    application::start,
+    core::{Color, TextWeight, Widget, label, link, rich_text, span, vstack, window::Window},
+};
+
+fn main() -> glazeui::Result {
+    let init = Links {
+        opened: String::new(),
+    };
+
+    start(init, Links::view, Links::update).title("Links").run()
+}
+
+struct Links {
+    opened: String,
+}
+
+#[derive(Clone)]
+enum Message {
+    OpenLink(String),
+}
+
+impl Links {
+    fn update(&mut self, message: Message, _: &mut Window) {
+        match message {
+            Message::OpenLink(url) => self.opened = url,
+        }
+    }
+
+    fn view(&mut self, _: &mut Window) -> Widget<Message> {
+        let text = rich_text(vec![
+            span("GlazeUI is inspired by "),
+            link("Iced", "https://github.com/iced-rs/iced"),
+            span(" and renders with "),
+            link("MultiRender", "https://github.com/dest-hq/multirender"),
+            span("!").weight(TextWeight::BOLD),
+        ])
+        .size(24)
+        .link_color(Color::rgb(54, 104, 237))
+        .on_link(Message::OpenLink) // Every link click sends its url
+        .build();
+
+        let opened = label(&format!("Clicked: {}", self.opened)).size(18).build();
+
+        vstack!(text, opened).spacing(20).build()
+    }
+}
@@ -0,0 +1,49 @@
+use glazeui::{
+    application::start,
+    core::{Widget, markdown, window::Window},
+};
+
+const RELEASE_NOTES: &str = r#"# Release notes
+
+GlazeUI now renders **markdown** with *emphasis*, ~~strikethrough~~ and `inline code`.
+
+## Changes
+
+1. Rich text with [links](https://github.com/dest-hq/glazeui)
+2. Markdown widget
+   - headings
+   - lists
+
+> Block quotes are drawn with an indent
+
+```
+fn main() -> glazeui::Result {
+    start(init, view, update).run()
+}
+```
+"#;
+
+fn main() -> glazeui::Result {
+    start(Notes {}, Notes::view, Notes::update)
+        .title("Markdown")
+        .run()
+}
+
+struct Notes {}
+
+#[derive(Clone)]
+enum Message {
+    OpenLink(String),
+}
+
+impl Notes {
+    fn update(&mut self, message: Message, _: &mut Window) {
+        match message {
+            Message::OpenLink(url) => println!("Clicked {url}"),
+        }
+    }
+
+    fn view(&mut self, _: &mut Window) -> Widget<Message> {
+        markdown(RELEASE_NOTES).on_link(Message::OpenLink).build()
+    }
+}
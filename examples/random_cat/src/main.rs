@@ -1,11 +1,12 @@
 use glazeui::{
     application::start,
-    core::{Widget, button, image, label, task::Task, vstack, window::Window},
+    core::{
+        Widget, button, image, image_handle::ImageHandle, label, task::Task, vstack, window::Window,
+    },
 };
-use image::EncodableLayout;
 
 fn main() -> glazeui::Result {
-    let init = RandomCat { image_bytes: None };
+    let init = RandomCat { cat_image: None };
 
     start(init, RandomCat::view, RandomCat::update)
         .size(700, 700)
@@ -14,7 +15,7 @@ fn main() -> glazeui::Result {
 }
 
 struct RandomCat {
-    image_bytes: Option<Vec<u8>>,
+    cat_image: Option<ImageHandle>,
 }
 
 #[derive(Clone)]
@@ -37,18 +38,15 @@ impl RandomCat {
         match message {
             Message::GetBytesOfCatImage => Task::new(async { get_cat_image().await }),
             Message::UpdateBytesOfCatImage(bytes) => {
-                self.image_bytes = Some(bytes);
+                self.cat_image = Some(ImageHandle::from_bytes(bytes));
                 Task::none()
             }
         }
     }
 
     fn view(&mut self, _: &mut Window) -> Widget<Message> {
-        let cat_image = if let Some(bytes) = &self.image_bytes {
-            image()
-                .from_bytes(bytes.as_bytes(), Some(700), Some(500))
-                .unwrap()
-                .build()
+        let cat_image = if let Some(cat_image) = &self.cat_image {
+            image().handle(cat_image, Some(700), Some(500)).build()
         } else {
             label("No cat image").size(25).build()
         };
@@ -3,16 +3,17 @@ use glazeui_core::task::Task;
 use glazeui_winit::event::UserEvent;
 
 use crate::core::{
-    Backend, Color, Widget,
+    Backend, Background, Color, Direction, Widget,
     window::{Theme, WindowLevel},
 };
 use crate::shell::{Application, Program, Renderer};
-use glazeui_core::window::Window;
+use glazeui_core::{image_handle::on_image_loaded, window::Window};
 use glazeui_layout::LayoutEngine;
-use glazeui_render::RenderState;
+use glazeui_render::{RenderState, damage::DamageTracker};
 use parley::{FontContext, LayoutContext, fontique::Blob};
+use std::sync::Mutex;
 use winit::{
-    dpi::{PhysicalPosition, PhysicalSize, Size},
+    dpi::{LogicalPosition, LogicalSize, Size},
     event_loop::EventLoop,
     window::{Theme as WinitTheme, WindowAttributes, WindowLevel as WinitWindowLevel},
 };
@@ -29,7 +30,8 @@ pub fn start<M: Clone + Send + 'static, App>(
 
 struct WindowSettings {
     attributes: WindowAttributes,
-    background: Color,
+    background: Background,
+    direction: Direction,
 }
 
 pub struct Run<M: Clone + Send + 'static, App: 'static> {
@@ -79,12 +81,13 @@ impl<M: Clone + Send + 'static, App: 'static> Run<M, App> {
             user_struct: user_struct,
             window_settings: WindowSettings {
                 attributes: WindowAttributes::default()
-                    .with_inner_size(Size::Physical(PhysicalSize {
-                        width: 800,
-                        height: 600,
+                    .with_inner_size(Size::Logical(LogicalSize {
+                        width: 800.0,
+                        height: 600.0,
                     }))
                     .with_title("GlazeUI"),
-                background: Color::rgb(0, 0, 0),
+                background: Background::Color(Color::rgb(0, 0, 0)),
+                direction: Direction::Ltr,
             },
             view_fn: view_fn,
             update_fn: update_fn,
@@ -108,40 +111,50 @@ impl<M: Clone + Send + 'static, App: 'static> Run<M, App> {
         self
     }
 
-    pub fn background(mut self, background: Color) -> Self {
-        self.window_settings.background = background;
+    /// Window background, a solid color or a gradient
+    pub fn background(mut self, background: impl Into<Background>) -> Self {
+        self.window_settings.background = background.into();
         self
     }
 
+    /// Direction of the app, `Direction::Rtl` for Arabic or Hebrew mirrors the whole layout
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.window_settings.direction = direction;
+        self
+    }
+
+    /// Window size in logical pixels, it is scaled on HiDPI displays
     pub fn size(mut self, width: u32, height: u32) -> Self {
         self.window_settings.attributes =
             self.window_settings
                 .attributes
-                .with_inner_size(Size::Physical(PhysicalSize {
-                    width: width,
-                    height: height,
+                .with_inner_size(Size::Logical(LogicalSize {
+                    width: width as f64,
+                    height: height as f64,
                 }));
         self
     }
 
+    /// Largest window size in logical pixels
     pub fn max_size(mut self, width: u32, height: u32) -> Self {
         self.window_settings.attributes =
             self.window_settings
                 .attributes
-                .with_max_inner_size(Size::Physical(PhysicalSize {
-                    width: width,
-                    height: height,
+                .with_max_inner_size(Size::Logical(LogicalSize {
+                    width: width as f64,
+                    height: height as f64,
                 }));
         self
     }
 
+    /// Smallest window size in logical pixels
     pub fn min_size(mut self, width: u32, height: u32) -> Self {
         self.window_settings.attributes =
             self.window_settings
                 .attributes
-                .with_min_inner_size(Size::Physical(PhysicalSize {
-                    width: width,
-                    height: height,
+                .with_min_inner_size(Size::Logical(LogicalSize {
+                    width: width as f64,
+                    height: height as f64,
                 }));
         self
     }
@@ -200,6 +213,14 @@ impl<M: Clone + Send + 'static, App: 'static> Run<M, App> {
         #[cfg(feature = "async")]
         let proxy = event_loop.create_proxy();
 
+        // Redraw when an image decoded in background is ready
+        let image_proxy = Mutex::new(event_loop.create_proxy());
+        on_image_loaded(move || {
+            if let Ok(proxy) = image_proxy.lock() {
+                proxy.send_event(UserEvent::ImageLoaded).ok();
+            }
+        });
+
         let size = self
             .window_settings
             .attributes
@@ -237,14 +258,19 @@ impl<M: Clone + Send + 'static, App: 'static> Run<M, App> {
                 font_context: font_context,
                 registred_fallback_font,
                 layout_context: LayoutContext::new(),
-                layout: LayoutEngine::new(),
+                layout: LayoutEngine::new().direction(self.window_settings.direction),
+                damage: DamageTracker::new(),
+                frame: None,
+                animation_frame: false,
+                next_animation_frame: None,
             },
             application: Application {
                 user_struct: self.user_struct,
                 view_fn: self.view_fn,
                 update_fn: self.update_fn,
                 background: self.window_settings.background,
-                position: PhysicalPosition::new(0.0, 0.0),
+                position: LogicalPosition::new(0.0, 0.0),
+                hovered_link: None,
             },
         };
 
@@ -1,3 +1,30 @@
 use winit::error::EventLoopError;
 
 pub type Error = EventLoopError;
+
+/// Error of `render_to_image`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    /// Scale is zero, negative, infinite or not a number
+    InvalidScale(f32),
+    /// Width or height of the image is zero
+    EmptyImage { width: u32, height: u32 },
+    /// Renderer returned fewer pixels than the image has
+    BufferSize { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::InvalidScale(scale) => write!(f, "can't render at scale {scale}"),
+            RenderError::EmptyImage { width, height } => {
+                write!(f, "can't render an empty {width}x{height} image")
+            }
+            RenderError::BufferSize { expected, actual } => {
+                write!(f, "renderer returned {actual} bytes instead of {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
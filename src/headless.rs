@@ -0,0 +1,251 @@
+#[cfg(feature = "cpu")]
+use crate::error::RenderError;
+use glazeui_core::{Background, Color, Widget, id::with_id_scope, image_handle::load_blocking_in};
+use glazeui_layout::LayoutEngine;
+use glazeui_render::{draw::draw, svg::SvgScene, widgets::draw_rect::draw_rectangle};
+#[cfg(feature = "cpu")]
+use image::RgbaImage;
+use kurbo::Affine;
+#[cfg(feature = "cpu")]
+use multirender::ImageRenderer;
+use multirender::PaintScene;
+#[cfg(feature = "cpu")]
+use multirender_vello_cpu::VelloCpuImageRenderer;
+use parley::{
+    FontContext, LayoutContext,
+    fontique::{Blob, Collection, CollectionOptions, SourceCache},
+};
+use std::time::Instant;
+
+/// Render a view to an image on the CPU, without a window or a GPU
+///
+/// `width` and `height` are in logical pixels like the layout, the image is `scale` times bigger.
+/// Text only uses the bundled Inter font, so the image is the same on every machine.
+/// The background is black, like the default window background.
+/// The view gets no `Window`, views that take one can move their widgets to a function without it.
+/// Images from `ImageHandle` are decoded before drawing, so they are in the image.
+///
+/// ```ignore
+/// let image = glazeui::render_to_image(&mut counter, |counter| counter.content(), 400, 300, 2.0)?;
+/// image.save("screen.png").unwrap();
+/// ```
+#[cfg(feature = "cpu")]
+pub fn render_to_image<M: Clone + Send + 'static, App>(
+    app: &mut App,
+    view_fn: impl FnOnce(&mut App) -> Widget<M>,
+    width: u32,
+    height: u32,
+    scale: f32,
+) -> Result<RgbaImage, RenderError> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(RenderError::InvalidScale(scale));
+    }
+    let physical_width = (width as f32 * scale).round() as u32;
+    let physical_height = (height as f32 * scale).round() as u32;
+    if physical_width == 0 || physical_height == 0 {
+        return Err(RenderError::EmptyImage {
+            width: physical_width,
+            height: physical_height,
+        });
+    }
+
+    let (ui, layout) = layout_view(app, view_fn, width, height, scale);
+
+    let mut renderer = VelloCpuImageRenderer::new(physical_width, physical_height);
+    let mut buffer = Vec::with_capacity((physical_width * physical_height * 4) as usize);
+    renderer.render_to_vec(
+        |scene| {
+            draw_view(
+                scene,
+                &layout,
+                &ui,
+                physical_width as f64,
+                physical_height as f64,
+                scale as f64,
+            );
+        },
+        &mut buffer,
+    );
+
+    let expected = physical_width as usize * physical_height as usize * 4;
+    let actual = buffer.len();
+    RgbaImage::from_raw(physical_width, physical_height, buffer)
+        .ok_or(RenderError::BufferSize { expected, actual })
+}
+
+/// Export a view as an SVG document, for printing or documentation
+///
+/// `width` and `height` are in logical pixels like the layout, text is saved as outlines
+/// of the bundled Inter font. Like `render_to_image`, the background is black and the view gets no `Window`.
+///
+/// ```ignore
+/// let svg = glazeui::render_to_svg(&mut counter, |counter| counter.content(), 400, 300);
+/// std::fs::write("screen.svg", svg).unwrap();
+/// ```
+pub fn render_to_svg<M: Clone + Send + 'static, App>(
+    app: &mut App,
+    view_fn: impl FnOnce(&mut App) -> Widget<M>,
+    width: u32,
+    height: u32,
+) -> String {
+    let (ui, layout) = layout_view(app, view_fn, width, height, 1.0);
+
+    let mut scene = SvgScene::new();
+    draw_view(&mut scene, &layout, &ui, width as f64, height as f64, 1.0);
+    scene.to_svg(width as f64, height as f64)
+}
+
+/// Build the view and lay it out without a window
+fn layout_view<M: Clone + Send + 'static, App>(
+    app: &mut App,
+    view_fn: impl FnOnce(&mut App) -> Widget<M>,
+    width: u32,
+    height: u32,
+    scale: f32,
+) -> (Widget<M>, LayoutEngine<M>) {
+    // Ids start from the beginning like every frame in a window, without resetting
+    // the ids of windows or other renders.
+    // There is only one frame, images can't be drawn later when they are decoded
+    let ui = with_id_scope(|| load_blocking_in(|| view_fn(app)));
+
+    let mut font_context = FontContext {
+        collection: Collection::new(CollectionOptions {
+            shared: false,
+            system_fonts: false,
+        }),
+        source_cache: SourceCache::default(),
+    };
+    let font_blob = Blob::from(include_bytes!("assets/fonts/Inter.ttf").to_vec());
+    font_context.collection.register_fonts(font_blob, None);
+    let mut layout_context = LayoutContext::new();
+
+    let mut layout = LayoutEngine::new();
+    layout.compute(
+        &ui,
+        width as f32,
+        height as f32,
+        scale,
+        true,
+        &mut font_context,
+        &mut layout_context,
+    );
+    (ui, layout)
+}
+
+/// Draw the background and the view, `width` and `height` are the size of the scene
+fn draw_view<M: Clone + Send + 'static, T: PaintScene>(
+    scene: &mut T,
+    layout: &LayoutEngine<M>,
+    ui: &Widget<M>,
+    width: f64,
+    height: f64,
+    scale: f64,
+) {
+    let background = Background::Color(Color::rgb(0, 0, 0));
+    draw_rectangle(
+        scene,
+        Affine::IDENTITY,
+        0.0,
+        &background,
+        0.0,
+        0.0,
+        width,
+        height,
+    ); // Background
+
+    draw(
+        scene,
+        layout,
+        None,
+        ui,
+        Affine::scale(scale),
+        Instant::now(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glazeui_core::{Length, Margin, container, spacer};
+
+    /// Red 100x50 box with a 20px blue box inside, moved 10px from the corner
+    fn boxes(_: &mut ()) -> Widget<()> {
+        let inner = container(spacer().width(20).height(20).build())
+            .width(Length::Shrink)
+            .height(Length::Shrink)
+            .color(Color::rgb(0, 0, 255))
+            .margin(Margin::new().left(10).top(10))
+            .build();
+        container(inner)
+            .color(Color::rgb(255, 0, 0))
+            .width(100)
+            .height(50)
+            .build()
+    }
+
+    #[cfg(feature = "cpu")]
+    fn pixel(image: &RgbaImage, x: u32, y: u32) -> [u8; 4] {
+        image.get_pixel(x, y).0
+    }
+
+    #[test]
+    #[cfg(feature = "cpu")]
+    fn renders_boxes() {
+        let image = render_to_image(&mut (), boxes, 200, 100, 1.0).unwrap();
+        assert_eq!(image.dimensions(), (200, 100));
+
+        assert_eq!(pixel(&image, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 20, 20), [0, 0, 255, 255]);
+        assert_eq!(pixel(&image, 95, 45), [255, 0, 0, 255]);
+        // Background outside of the boxes
+        assert_eq!(pixel(&image, 150, 20), [0, 0, 0, 255]);
+        assert_eq!(pixel(&image, 50, 80), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    #[cfg(feature = "cpu")]
+    fn scales_image() {
+        let image = render_to_image(&mut (), boxes, 200, 100, 2.0).unwrap();
+        assert_eq!(image.dimensions(), (400, 200));
+
+        assert_eq!(pixel(&image, 190, 90), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 40, 40), [0, 0, 255, 255]);
+        assert_eq!(pixel(&image, 210, 20), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    #[cfg(feature = "cpu")]
+    fn empty_image_is_error() {
+        assert_eq!(
+            render_to_image(&mut (), boxes, 0, 100, 1.0).unwrap_err(),
+            RenderError::EmptyImage {
+                width: 0,
+                height: 100
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cpu")]
+    fn invalid_scale_is_error() {
+        for scale in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                render_to_image(&mut (), boxes, 200, 100, scale),
+                Err(RenderError::InvalidScale(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn renders_svg() {
+        let svg = render_to_svg(&mut (), boxes, 200, 100);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"width="200" height="100" viewBox="0 0 200 100""#));
+        // Background, then the red and the blue box
+        let black = svg.find("rgb(0,0,0)").unwrap();
+        let red = svg.find("rgb(255,0,0)").unwrap();
+        let blue = svg.find("rgb(0,0,255)").unwrap();
+        assert!(black < red && red < blue);
+        assert!(svg.ends_with("</svg>"));
+    }
+}
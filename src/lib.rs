@@ -2,6 +2,10 @@ use crate::error::Error;
 pub mod error;
 
 pub mod application;
+mod headless;
+#[cfg(feature = "cpu")]
+pub use headless::render_to_image;
+pub use headless::render_to_svg;
 
 pub mod layout {
     pub use glazeui_layout::*;